@@ -1,2 +1,3 @@
+pub mod protocol;
 pub mod utils;
 pub mod zappy;
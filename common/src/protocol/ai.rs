@@ -0,0 +1,240 @@
+use {
+  crate::{
+    protocol::{Error, Result, words::Words},
+    zappy::{Inventory, Item, Look, TileView},
+  },
+  std::{fmt, str::FromStr},
+};
+
+/// A command sent by an AI client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AiCommand {
+  Forward,
+  Right,
+  Left,
+  Look,
+  Inventory,
+  Broadcast(String),
+  ConnectNbr,
+  Fork,
+  Eject,
+  Take(Item),
+  Set(Item),
+  Incantation,
+}
+
+/// What a `TileView` puts on the wire: the tile's contents, since a `Look` reply leaves positions
+/// implicit in the order of its tiles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LookTile {
+  pub players: usize,
+  pub items: Inventory,
+}
+
+/// A message sent by the server to an AI client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AiResponse {
+  Welcome,
+  Ok,
+  Ko,
+  Dead,
+  /// Free slots of the team, after joining it and in reply to `Connect_nbr`.
+  Slots(usize),
+  MapSize(usize, usize),
+  /// Seen tiles, nearest row first. Never empty, since a player always sees its own tile.
+  Look(Vec<LookTile>),
+  Inventory(Inventory),
+  Message(u8, String),
+  Eject(u8),
+  ElevationUnderway,
+  CurrentLevel(u8),
+}
+
+impl AiCommand {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Forward => "Forward",
+      Self::Right => "Right",
+      Self::Left => "Left",
+      Self::Look => "Look",
+      Self::Inventory => "Inventory",
+      Self::Broadcast(_) => "Broadcast",
+      Self::ConnectNbr => "Connect_nbr",
+      Self::Fork => "Fork",
+      Self::Eject => "Eject",
+      Self::Take(_) => "Take",
+      Self::Set(_) => "Set",
+      Self::Incantation => "Incantation",
+    }
+  }
+}
+
+impl fmt::Display for AiCommand {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())?;
+    match self {
+      Self::Broadcast(text) => write!(f, " {text}"),
+      Self::Take(item) | Self::Set(item) => write!(f, " {item}"),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl FromStr for AiCommand {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    use AiCommand::*;
+    let (name, mut words) = Words::new(s);
+    let command = match name {
+      "Forward" => Forward,
+      "Right" => Right,
+      "Left" => Left,
+      "Look" => Look,
+      "Inventory" => Inventory,
+      "Connect_nbr" => ConnectNbr,
+      "Fork" => Fork,
+      "Eject" => Eject,
+      "Incantation" => Incantation,
+      "Broadcast" => match words.rest() {
+        "" => return Err(words.invalid()),
+        text => Broadcast(text.to_string()),
+      },
+      "Take" => Take(words.next()?),
+      "Set" => Set(words.next()?),
+      _ => return Err(Error::UnknownMessage(s.to_string())),
+    };
+    words.end(command)
+  }
+}
+
+impl From<&TileView> for LookTile {
+  fn from(view: &TileView) -> Self {
+    Self {
+      players: view.players,
+      items: view.items,
+    }
+  }
+}
+
+impl fmt::Display for LookTile {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let players = (0..self.players).map(|_| "player");
+    let items = self
+      .items
+      .iter()
+      .flat_map(|(item, count)| (0..count).map(move |_| item.name()));
+    let words = players.chain(items).collect::<Vec<_>>();
+    f.write_str(&words.join(" "))
+  }
+}
+
+impl FromStr for LookTile {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    let mut tile = Self::default();
+    for word in s.split_whitespace() {
+      match word {
+        "player" => tile.players += 1,
+        item => tile.items.add(
+          item
+            .parse()
+            .map_err(|_| Error::InvalidArguments(s.to_string()))?,
+          1,
+        ),
+      }
+    }
+    Ok(tile)
+  }
+}
+
+impl From<&Look> for AiResponse {
+  fn from(look: &Look) -> Self {
+    Self::Look(look.0.iter().map(LookTile::from).collect())
+  }
+}
+
+impl fmt::Display for AiResponse {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Welcome => f.write_str("WELCOME"),
+      Self::Ok => f.write_str("ok"),
+      Self::Ko => f.write_str("ko"),
+      Self::Dead => f.write_str("dead"),
+      Self::Slots(slots) => write!(f, "{slots}"),
+      Self::MapSize(x, y) => write!(f, "{x} {y}"),
+      Self::Look(tiles) => {
+        let tiles = tiles.iter().map(ToString::to_string).collect::<Vec<_>>();
+        write!(f, "[{}]", tiles.join(", "))
+      }
+      Self::Inventory(inventory) => {
+        let items = inventory
+          .iter()
+          .map(|(item, count)| format!("{item} {count}"))
+          .collect::<Vec<_>>();
+        write!(f, "[{}]", items.join(", "))
+      }
+      Self::Message(direction, text) => write!(f, "message {direction}, {text}"),
+      Self::Eject(direction) => write!(f, "eject: {direction}"),
+      Self::ElevationUnderway => f.write_str("Elevation underway"),
+      Self::CurrentLevel(level) => write!(f, "Current level: {level}"),
+    }
+  }
+}
+
+impl FromStr for AiResponse {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    let invalid = || Error::InvalidArguments(s.to_string());
+    let response = match s {
+      "WELCOME" => Self::Welcome,
+      "ok" => Self::Ok,
+      "ko" => Self::Ko,
+      "dead" => Self::Dead,
+      "Elevation underway" => Self::ElevationUnderway,
+      _ if s.starts_with('[') => {
+        let entries = s
+          .strip_prefix('[')
+          .and_then(|s| s.strip_suffix(']'))
+          .ok_or_else(invalid)?
+          .split(',')
+          .map(str::trim)
+          .collect::<Vec<_>>();
+        match inventory(&entries) {
+          Some(inventory) => Self::Inventory(inventory),
+          None => Self::Look(entries.iter().map(|e| e.parse()).collect::<Result<_>>()?),
+        }
+      }
+      _ => {
+        if let Some(rest) = s.strip_prefix("message ") {
+          let (direction, text) = rest.split_once(", ").ok_or_else(invalid)?;
+          Self::Message(direction.parse().map_err(|_| invalid())?, text.to_string())
+        } else if let Some(direction) = s.strip_prefix("eject: ") {
+          Self::Eject(direction.parse().map_err(|_| invalid())?)
+        } else if let Some(level) = s.strip_prefix("Current level: ") {
+          Self::CurrentLevel(level.parse().map_err(|_| invalid())?)
+        } else if let Some((x, y)) = s.split_once(' ') {
+          match (x.parse(), y.parse()) {
+            (Ok(x), Ok(y)) => Self::MapSize(x, y),
+            _ => return Err(Error::UnknownMessage(s.to_string())),
+          }
+        } else {
+          Self::Slots(
+            s.parse()
+              .map_err(|_| Error::UnknownMessage(s.to_string()))?,
+          )
+        }
+      }
+    };
+    Ok(response)
+  }
+}
+
+/// Reads `[name count, ...]` entries, or nothing if they aren't an inventory but a look.
+fn inventory(entries: &[&str]) -> Option<Inventory> {
+  let mut inventory = Inventory::new();
+  for entry in entries {
+    let (item, count) = entry.split_once(' ')?;
+    inventory.add(item.parse::<Item>().ok()?, count.parse().ok()?);
+  }
+  Some(inventory)
+}
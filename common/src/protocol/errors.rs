@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Error {
+  #[error("unknown message '{0}'")]
+  UnknownMessage(String),
+
+  #[error("invalid arguments in '{0}'")]
+  InvalidArguments(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,259 @@
+use {
+  crate::{
+    protocol::{Error, Result, words::Words},
+    zappy::{Inventory, Item, Orientation, Position, constants::ITEM_KINDS},
+  },
+  std::{fmt, str::FromStr},
+};
+
+/// A query sent by a graphical client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuiCommand {
+  Msz,
+  Bct(Position),
+  Mct,
+  Tna,
+  Ppo(usize),
+  Plv(usize),
+  Pin(usize),
+  Sgt,
+  Sst(u64),
+}
+
+/// A message sent by the server to a graphical client, in reply to a query or as an event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuiEvent {
+  Msz(usize, usize),
+  Bct(Position, Inventory),
+  Tna(String),
+  Pnw {
+    id: usize,
+    position: Position,
+    orientation: Orientation,
+    level: u8,
+    team: String,
+  },
+  Ppo(usize, Position, Orientation),
+  Plv(usize, u8),
+  Pin(usize, Position, Inventory),
+  Pex(usize),
+  Pbc(usize, String),
+  Pic(Position, u8, Vec<usize>),
+  Pie(Position, bool),
+  Pfk(usize),
+  Pdr(usize, Item),
+  Pgt(usize, Item),
+  Pdi(usize),
+  /// Egg laid by a player, or one a team started with when there's no player.
+  Enw(usize, Option<usize>, Position),
+  Ebo(usize),
+  Edi(usize),
+  Sgt(u64),
+  Sst(u64),
+  Seg(String),
+  Smg(String),
+  Suc,
+  Sbp,
+}
+
+impl fmt::Display for GuiCommand {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Msz => f.write_str("msz"),
+      Self::Bct(position) => write!(f, "bct {} {}", position.x, position.y),
+      Self::Mct => f.write_str("mct"),
+      Self::Tna => f.write_str("tna"),
+      Self::Ppo(id) => write!(f, "ppo #{id}"),
+      Self::Plv(id) => write!(f, "plv #{id}"),
+      Self::Pin(id) => write!(f, "pin #{id}"),
+      Self::Sgt => f.write_str("sgt"),
+      Self::Sst(frequency) => write!(f, "sst {frequency}"),
+    }
+  }
+}
+
+impl FromStr for GuiCommand {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    let (name, mut words) = Words::new(s);
+    let command = match name {
+      "msz" => Self::Msz,
+      "bct" => Self::Bct(position(&mut words)?),
+      "mct" => Self::Mct,
+      "tna" => Self::Tna,
+      "ppo" => Self::Ppo(words.id()?),
+      "plv" => Self::Plv(words.id()?),
+      "pin" => Self::Pin(words.id()?),
+      "sgt" => Self::Sgt,
+      "sst" => Self::Sst(words.next()?),
+      _ => return Err(Error::UnknownMessage(s.to_string())),
+    };
+    words.end(command)
+  }
+}
+
+impl fmt::Display for GuiEvent {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Msz(x, y) => write!(f, "msz {x} {y}"),
+      Self::Bct(p, items) => write!(f, "bct {} {} {}", p.x, p.y, quantities(items)),
+      Self::Tna(team) => write!(f, "tna {team}"),
+      Self::Pnw {
+        id,
+        position: p,
+        orientation: o,
+        level,
+        team,
+      } => write!(f, "pnw #{id} {} {} {} {level} {team}", p.x, p.y, number(*o)),
+      Self::Ppo(id, p, o) => write!(f, "ppo #{id} {} {} {}", p.x, p.y, number(*o)),
+      Self::Plv(id, level) => write!(f, "plv #{id} {level}"),
+      Self::Pin(id, p, items) => write!(f, "pin #{id} {} {} {}", p.x, p.y, quantities(items)),
+      Self::Pex(id) => write!(f, "pex #{id}"),
+      Self::Pbc(id, message) => write!(f, "pbc #{id} {message}"),
+      Self::Pic(p, level, players) => {
+        write!(f, "pic {} {} {level}", p.x, p.y)?;
+        players.iter().try_for_each(|id| write!(f, " #{id}"))
+      }
+      Self::Pie(p, success) => write!(f, "pie {} {} {}", p.x, p.y, u8::from(*success)),
+      Self::Pfk(id) => write!(f, "pfk #{id}"),
+      Self::Pdr(id, item) => write!(f, "pdr #{id} {}", item.index()),
+      Self::Pgt(id, item) => write!(f, "pgt #{id} {}", item.index()),
+      Self::Pdi(id) => write!(f, "pdi #{id}"),
+      Self::Enw(egg, player, p) => match player {
+        Some(player) => write!(f, "enw #{egg} #{player} {} {}", p.x, p.y),
+        None => write!(f, "enw #{egg} #-1 {} {}", p.x, p.y),
+      },
+      Self::Ebo(egg) => write!(f, "ebo #{egg}"),
+      Self::Edi(egg) => write!(f, "edi #{egg}"),
+      Self::Sgt(frequency) => write!(f, "sgt {frequency}"),
+      Self::Sst(frequency) => write!(f, "sst {frequency}"),
+      Self::Seg(team) => write!(f, "seg {team}"),
+      Self::Smg(message) => write!(f, "smg {message}"),
+      Self::Suc => f.write_str("suc"),
+      Self::Sbp => f.write_str("sbp"),
+    }
+  }
+}
+
+impl FromStr for GuiEvent {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    let (name, mut words) = Words::new(s);
+    let w = &mut words;
+    let event = match name {
+      "msz" => Self::Msz(w.next()?, w.next()?),
+      "bct" => Self::Bct(position(w)?, quantities_from(w)?),
+      "tna" => Self::Tna(text(w)?),
+      "pnw" => Self::Pnw {
+        id: w.id()?,
+        position: position(w)?,
+        orientation: orientation(w)?,
+        level: w.next()?,
+        team: text(w)?,
+      },
+      "ppo" => Self::Ppo(w.id()?, position(w)?, orientation(w)?),
+      "plv" => Self::Plv(w.id()?, w.next()?),
+      "pin" => Self::Pin(w.id()?, position(w)?, quantities_from(w)?),
+      "pex" => Self::Pex(w.id()?),
+      "pbc" => Self::Pbc(w.id()?, w.rest().to_string()),
+      "pic" => {
+        let (position, level) = (position(w)?, w.next()?);
+        let players = w
+          .remaining()
+          .map(|id| id.strip_prefix('#').unwrap_or(id).parse())
+          .collect::<std::result::Result<_, _>>()
+          .map_err(|_| Error::InvalidArguments(s.to_string()))?;
+        Self::Pic(position, level, players)
+      }
+      "pie" => Self::Pie(
+        position(w)?,
+        match w.next::<u8>()? {
+          0 => false,
+          1 => true,
+          _ => return Err(w.invalid()),
+        },
+      ),
+      "pfk" => Self::Pfk(w.id()?),
+      "pdr" => Self::Pdr(w.id()?, resource(w)?),
+      "pgt" => Self::Pgt(w.id()?, resource(w)?),
+      "pdi" => Self::Pdi(w.id()?),
+      "enw" => {
+        let egg = w.id()?;
+        let player = match w.next::<String>()?.as_str() {
+          "#-1" | "-1" => None,
+          player => Some(
+            player
+              .strip_prefix('#')
+              .unwrap_or(player)
+              .parse()
+              .map_err(|_| Error::InvalidArguments(s.to_string()))?,
+          ),
+        };
+        Self::Enw(egg, player, position(w)?)
+      }
+      "ebo" => Self::Ebo(w.id()?),
+      "edi" => Self::Edi(w.id()?),
+      "sgt" => Self::Sgt(w.next()?),
+      "sst" => Self::Sst(w.next()?),
+      "seg" => Self::Seg(text(w)?),
+      "smg" => Self::Smg(w.rest().to_string()),
+      "suc" => Self::Suc,
+      "sbp" => Self::Sbp,
+      _ => return Err(Error::UnknownMessage(s.to_string())),
+    };
+    words.end(event)
+  }
+}
+
+/// Orientation number used by the graphical protocol, from 1 for north to 4 for west.
+fn number(orientation: Orientation) -> u8 {
+  match orientation {
+    Orientation::North => 1,
+    Orientation::East => 2,
+    Orientation::South => 3,
+    Orientation::West => 4,
+  }
+}
+
+fn orientation(words: &mut Words) -> Result<Orientation> {
+  Ok(match words.next::<u8>()? {
+    1 => Orientation::North,
+    2 => Orientation::East,
+    3 => Orientation::South,
+    4 => Orientation::West,
+    _ => return Err(words.invalid()),
+  })
+}
+
+fn position(words: &mut Words) -> Result<Position> {
+  Ok(Position::new(words.next()?, words.next()?))
+}
+
+fn resource(words: &mut Words) -> Result<Item> {
+  Item::try_from(words.next::<usize>()?).map_err(|_| words.invalid())
+}
+
+/// Team names and other trailing text, which can't be empty.
+fn text(words: &mut Words) -> Result<String> {
+  match words.rest() {
+    "" => Err(words.invalid()),
+    text => Ok(text.to_string()),
+  }
+}
+
+/// Quantities in resource number order, food first.
+fn quantities(inventory: &Inventory) -> String {
+  let counts = inventory
+    .iter()
+    .map(|(_, count)| count.to_string())
+    .collect::<Vec<_>>();
+  counts.join(" ")
+}
+
+fn quantities_from(words: &mut Words) -> Result<Inventory> {
+  let mut counts = [0; ITEM_KINDS];
+  for count in &mut counts {
+    *count = words.next()?;
+  }
+  Ok(Inventory::from(counts))
+}
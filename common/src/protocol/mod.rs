@@ -0,0 +1,6 @@
+mod ai;
+mod errors;
+mod gui;
+mod words;
+
+pub use {ai::*, errors::*, gui::*};
@@ -0,0 +1,55 @@
+use {
+  crate::protocol::{Error, Result},
+  std::str::{FromStr, SplitWhitespace},
+};
+
+/// Reads the space separated arguments following a message's name.
+pub(crate) struct Words<'a> {
+  line: &'a str,
+  rest: &'a str,
+}
+
+impl<'a> Words<'a> {
+  /// Splits off the message's name.
+  pub fn new(line: &'a str) -> (&'a str, Self) {
+    let (name, rest) = line.split_once(' ').unwrap_or((line, ""));
+    (name, Self { line, rest })
+  }
+
+  pub fn invalid(&self) -> Error {
+    Error::InvalidArguments(self.line.to_string())
+  }
+
+  pub fn next<T: FromStr>(&mut self) -> Result<T> {
+    let (word, rest) = self.rest.split_once(' ').unwrap_or((self.rest, ""));
+    self.rest = rest;
+    word.parse().map_err(|_| self.invalid())
+  }
+
+  /// Player or egg number, written `#n`.
+  pub fn id(&mut self) -> Result<usize> {
+    let word = self.next::<String>()?;
+    word
+      .strip_prefix('#')
+      .unwrap_or(&word)
+      .parse()
+      .map_err(|_| self.invalid())
+  }
+
+  /// Whatever is left of the line, spaces included.
+  pub fn rest(&mut self) -> &'a str {
+    std::mem::take(&mut self.rest)
+  }
+
+  pub fn remaining(&mut self) -> SplitWhitespace<'a> {
+    self.rest().split_whitespace()
+  }
+
+  /// Fails unless every argument was read.
+  pub fn end<T>(&self, message: T) -> Result<T> {
+    match self.rest {
+      "" => Ok(message),
+      _ => Err(self.invalid()),
+    }
+  }
+}
@@ -1,3 +1,4 @@
 mod random;
+mod seeded_rng;
 
-pub use random::*;
+pub use {random::*, seeded_rng::*};
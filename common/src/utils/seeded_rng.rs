@@ -0,0 +1,51 @@
+use {
+  chacha20::{ChaCha12Rng, SerializedRngState},
+  rand::SeedableRng,
+  serde::{Deserialize, Deserializer, Serialize, Serializer, de},
+  std::ops::{Deref, DerefMut},
+};
+
+/// ChaCha12 generator, the algorithm behind `StdRng`, whose full state can be cloned and
+/// serialized so a restored game keeps drawing the same numbers.
+#[derive(Debug)]
+pub struct SeededRng(ChaCha12Rng);
+
+impl SeededRng {
+  pub fn seed_from_u64(seed: u64) -> Self {
+    Self(ChaCha12Rng::seed_from_u64(seed))
+  }
+}
+
+impl Clone for SeededRng {
+  fn clone(&self) -> Self {
+    Self(ChaCha12Rng::deserialize_state(&self.0.serialize_state()))
+  }
+}
+
+impl Deref for SeededRng {
+  type Target = ChaCha12Rng;
+  fn deref(&self) -> &ChaCha12Rng {
+    &self.0
+  }
+}
+
+impl DerefMut for SeededRng {
+  fn deref_mut(&mut self) -> &mut ChaCha12Rng {
+    &mut self.0
+  }
+}
+
+impl Serialize for SeededRng {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    self.0.serialize_state().as_slice().serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for SeededRng {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let state: SerializedRngState = Vec::<u8>::deserialize(deserializer)?
+      .try_into()
+      .map_err(|bytes: Vec<u8>| de::Error::invalid_length(bytes.len(), &"49 bytes of state"))?;
+    Ok(Self(ChaCha12Rng::deserialize_state(&state)))
+  }
+}
@@ -0,0 +1,29 @@
+use {
+  crate::zappy::{ResourceDensity, constants::DEFAULT_TEAM_CAPACITY},
+  serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorldConfig {
+  pub seed: u64,
+  pub width: usize,
+  pub height: usize,
+  pub teams: Vec<String>,
+  pub clients_per_team: usize,
+  pub frequency: u64,
+  pub density: ResourceDensity,
+}
+
+impl Default for WorldConfig {
+  fn default() -> Self {
+    Self {
+      seed: 0,
+      width: 10,
+      height: 10,
+      teams: Vec::new(),
+      clients_per_team: DEFAULT_TEAM_CAPACITY,
+      frequency: 100,
+      density: ResourceDensity::default(),
+    }
+  }
+}
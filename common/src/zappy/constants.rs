@@ -1,2 +1,8 @@
-pub const TEAM_SIZE: usize = 6;
+pub const DEFAULT_TEAM_CAPACITY: usize = 6;
 pub const ITEM_KINDS: usize = 7;
+pub const RESPAWN_INTERVAL: u64 = 20;
+pub const ORE_KINDS: usize = ITEM_KINDS - 1;
+pub const MAX_LEVEL: u8 = 8;
+pub const INITIAL_FOOD: usize = 10;
+pub const FOOD_TIME_UNITS: u64 = 126;
+pub const WINNING_PLAYERS: usize = 6;
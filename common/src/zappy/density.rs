@@ -0,0 +1,35 @@
+use {
+  crate::zappy::{Item, constants::ITEM_KINDS},
+  serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResourceDensity {
+  densities: [f64; ITEM_KINDS],
+}
+
+impl ResourceDensity {
+  pub fn new(densities: [f64; ITEM_KINDS]) -> Self {
+    Self { densities }
+  }
+
+  pub fn density(&self, item: Item) -> f64 {
+    self.densities[item.index()]
+  }
+
+  pub fn set_density(&mut self, item: Item, density: f64) {
+    self.densities[item.index()] = density;
+  }
+
+  /// Number of units of `item` a map of `area` tiles should hold, rounded down: a rare resource
+  /// can be missing from a small map.
+  pub fn quantity(&self, item: Item, area: usize) -> usize {
+    (area as f64 * self.density(item)) as usize
+  }
+}
+
+impl Default for ResourceDensity {
+  fn default() -> Self {
+    Self::new([0.5, 0.3, 0.15, 0.1, 0.1, 0.08, 0.05])
+  }
+}
@@ -0,0 +1,45 @@
+use {
+  crate::zappy::Position,
+  serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Egg {
+  id: usize,
+  team: String,
+  position: Position,
+  /// Player who laid it, none for the eggs a team starts with.
+  laid_by: Option<usize>,
+}
+
+impl Egg {
+  pub(crate) fn new(
+    id: usize,
+    team: impl Into<String>,
+    position: Position,
+    laid_by: Option<usize>,
+  ) -> Self {
+    Self {
+      id,
+      team: team.into(),
+      position,
+      laid_by,
+    }
+  }
+
+  pub fn id(&self) -> usize {
+    self.id
+  }
+
+  pub fn team(&self) -> &str {
+    &self.team
+  }
+
+  pub fn position(&self) -> Position {
+    self.position
+  }
+
+  pub fn laid_by(&self) -> Option<usize> {
+    self.laid_by
+  }
+}
@@ -0,0 +1,77 @@
+use {
+  crate::zappy::{
+    Position,
+    constants::{MAX_LEVEL, ORE_KINDS},
+  },
+  serde::{Deserialize, Serialize},
+};
+
+pub struct ElevationRequirement {
+  pub players: usize,
+  pub stones: [usize; ORE_KINDS],
+}
+
+const ELEVATIONS: [ElevationRequirement; MAX_LEVEL as usize - 1] = [
+  ElevationRequirement {
+    players: 1,
+    stones: [1, 0, 0, 0, 0, 0],
+  },
+  ElevationRequirement {
+    players: 2,
+    stones: [1, 1, 1, 0, 0, 0],
+  },
+  ElevationRequirement {
+    players: 2,
+    stones: [2, 0, 1, 0, 2, 0],
+  },
+  ElevationRequirement {
+    players: 4,
+    stones: [1, 1, 2, 0, 1, 0],
+  },
+  ElevationRequirement {
+    players: 4,
+    stones: [1, 2, 1, 3, 0, 0],
+  },
+  ElevationRequirement {
+    players: 6,
+    stones: [1, 2, 3, 0, 1, 0],
+  },
+  ElevationRequirement {
+    players: 6,
+    stones: [2, 2, 2, 2, 2, 1],
+  },
+];
+
+/// Requirements to elevate from `level` to `level + 1`, if such an elevation exists.
+pub fn elevation_requirement(level: u8) -> Option<&'static ElevationRequirement> {
+  ELEVATIONS.get((level as usize).checked_sub(1)?)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Incantation {
+  position: Position,
+  level: u8,
+  participants: Vec<usize>,
+}
+
+impl Incantation {
+  pub(crate) fn new(position: Position, level: u8, participants: Vec<usize>) -> Self {
+    Self {
+      position,
+      level,
+      participants,
+    }
+  }
+
+  pub fn position(&self) -> Position {
+    self.position
+  }
+
+  pub fn level(&self) -> u8 {
+    self.level
+  }
+
+  pub fn participants(&self) -> &[usize] {
+    &self.participants
+  }
+}
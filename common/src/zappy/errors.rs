@@ -1,10 +1,40 @@
-use thiserror::Error;
+use {
+  crate::zappy::{Item, Ore},
+  thiserror::Error,
+};
 
 #[derive(Error, Debug)]
 pub enum Error {
+  #[error("snapshot version {0} isn't supported")]
+  UnsupportedSnapshotVersion(u32),
+
+  #[error("invalid snapshot: {0}")]
+  InvalidSnapshot(String),
+
+  #[error("failed to encode or decode json snapshot: {0}")]
+  SnapshotJson(#[from] serde_json::Error),
+
+  #[error("failed to encode bincode snapshot: {0}")]
+  SnapshotEncode(#[from] bincode::error::EncodeError),
+
+  #[error("failed to decode bincode snapshot: {0}")]
+  SnapshotDecode(#[from] bincode::error::DecodeError),
+
   #[error("invalid ore number")]
   InvalidOreNumber,
 
+  #[error("invalid item number")]
+  InvalidItemNumber,
+
+  #[error("invalid item name '{0}'")]
+  InvalidItemName(String),
+
+  #[error("invalid map size {0}x{1}")]
+  InvalidMapSize(usize, usize),
+
+  #[error("invalid frequency {0}")]
+  InvalidFrequency(u64),
+
   #[error("team '{0}' already exists")]
   TeamExists(String),
 
@@ -14,8 +44,32 @@ pub enum Error {
   #[error("team '{0}' is full")]
   TeamIsFull(String),
 
-  #[error("player id {1} out of bounds for team '{0}'")]
-  PlayerOutOfBounds(String, usize),
+  #[error("player #{0} doesn't exist")]
+  PlayerDoesntExist(usize),
+
+  #[error("egg #{0} doesn't exist")]
+  EggDoesntExist(usize),
+
+  #[error("no {0} on the tile")]
+  ItemNotOnTile(Item),
+
+  #[error("no {0} in the inventory")]
+  ItemNotInInventory(Item),
+
+  #[error("player #{0} already reached the maximum level")]
+  MaxLevelReached(usize),
+
+  #[error("player #{0} is already leading an incantation")]
+  AlreadyIncanting(usize),
+
+  #[error("player #{0} isn't leading any incantation")]
+  NoIncantation(usize),
+
+  #[error("incantation requires {0} players of the same level, found {1}")]
+  NotEnoughPlayers(usize, usize),
+
+  #[error("incantation requires {1} {0}, found {2}")]
+  NotEnoughStones(Ore, usize, usize),
 
   #[error("no player with id {1} found in team '{0}'")]
   PlayerNotFound(String, usize),
@@ -0,0 +1,42 @@
+use {
+  crate::zappy::{Item, Position},
+  serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Event {
+  PlayerDied {
+    id: usize,
+    team: String,
+    position: Position,
+  },
+  PlayerEjected {
+    id: usize,
+    from: Position,
+    to: Position,
+    direction: u8,
+  },
+  ItemTaken {
+    id: usize,
+    item: Item,
+    position: Position,
+  },
+  ItemDropped {
+    id: usize,
+    item: Item,
+    position: Position,
+  },
+  EggDestroyed {
+    id: usize,
+    team: String,
+    position: Position,
+  },
+  /// Resources respawned on these tiles.
+  ResourcesSpawned { positions: Vec<Position> },
+  /// The leader left the game before the incantation's outcome.
+  IncantationAbandoned {
+    leader: usize,
+    position: Position,
+    participants: Vec<usize>,
+  },
+}
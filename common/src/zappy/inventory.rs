@@ -0,0 +1,62 @@
+use {
+  crate::zappy::{Item, constants::ITEM_KINDS},
+  serde::{Deserialize, Serialize},
+  std::ops::{Index, IndexMut},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Inventory([usize; ITEM_KINDS]);
+
+impl Inventory {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn count(&self, item: Item) -> usize {
+    self[item]
+  }
+
+  pub fn weight(&self) -> usize {
+    self.0.iter().sum()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.weight() == 0
+  }
+
+  pub fn add(&mut self, item: Item, amount: usize) {
+    self[item] += amount;
+  }
+
+  pub fn take(&mut self, item: Item, amount: usize) -> bool {
+    let count = &mut self[item];
+    if *count < amount {
+      return false;
+    }
+    *count -= amount;
+    true
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (Item, usize)> {
+    Item::iter().zip(self.0)
+  }
+}
+
+impl From<[usize; ITEM_KINDS]> for Inventory {
+  fn from(counts: [usize; ITEM_KINDS]) -> Self {
+    Self(counts)
+  }
+}
+
+impl Index<Item> for Inventory {
+  type Output = usize;
+  fn index(&self, item: Item) -> &usize {
+    &self.0[item.index()]
+  }
+}
+
+impl IndexMut<Item> for Inventory {
+  fn index_mut(&mut self, item: Item) -> &mut usize {
+    &mut self.0[item.index()]
+  }
+}
@@ -1,12 +1,17 @@
 use {
   crate::{
     utils::Random,
-    zappy::{Error, Result, constants::ITEM_KINDS},
+    zappy::{
+      Error, Result,
+      constants::{ITEM_KINDS, ORE_KINDS},
+    },
   },
   rand::{Rng, RngExt},
+  serde::{Deserialize, Serialize},
+  std::{fmt, str::FromStr},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Ore {
   Linemate,
   Deraumere,
@@ -16,28 +21,117 @@ pub enum Ore {
   Thystame,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Item {
   Nourriture,
   Ore(Ore),
 }
 
+impl Ore {
+  pub const ALL: [Ore; ORE_KINDS] = {
+    use Ore::*;
+    [Linemate, Deraumere, Sibur, Mendiane, Phiras, Thystame]
+  };
+
+  pub fn iter() -> impl Iterator<Item = Ore> {
+    Self::ALL.into_iter()
+  }
+
+  pub fn index(self) -> usize {
+    self as usize
+  }
+
+  pub fn name(self) -> &'static str {
+    use Ore::*;
+    match self {
+      Linemate => "linemate",
+      Deraumere => "deraumere",
+      Sibur => "sibur",
+      Mendiane => "mendiane",
+      Phiras => "phiras",
+      Thystame => "thystame",
+    }
+  }
+}
+
+impl Item {
+  pub const ALL: [Item; ITEM_KINDS] = [
+    Item::Nourriture,
+    Item::Ore(Ore::Linemate),
+    Item::Ore(Ore::Deraumere),
+    Item::Ore(Ore::Sibur),
+    Item::Ore(Ore::Mendiane),
+    Item::Ore(Ore::Phiras),
+    Item::Ore(Ore::Thystame),
+  ];
+
+  pub fn iter() -> impl Iterator<Item = Item> {
+    Self::ALL.into_iter()
+  }
+
+  /// Resource number used by the graphical protocol, also the item's slot in an `Inventory`.
+  pub fn index(self) -> usize {
+    match self {
+      Item::Nourriture => 0,
+      Item::Ore(ore) => ore.index() + 1,
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Item::Nourriture => "food",
+      Item::Ore(ore) => ore.name(),
+    }
+  }
+}
+
 impl TryFrom<u8> for Ore {
   type Error = Error;
   fn try_from(value: u8) -> Result<Self> {
-    use Ore::*;
-    match value {
-      0..=5 => Ok(match value {
-        0 => Linemate,
-        1 => Deraumere,
-        2 => Sibur,
-        3 => Mendiane,
-        4 => Phiras,
-        5 => Thystame,
-        _ => Linemate,
-      }),
-      _ => Err(Error::InvalidOreNumber),
-    }
+    Self::ALL
+      .get(value as usize)
+      .copied()
+      .ok_or(Error::InvalidOreNumber)
+  }
+}
+
+impl TryFrom<usize> for Item {
+  type Error = Error;
+  fn try_from(value: usize) -> Result<Self> {
+    Self::ALL
+      .get(value)
+      .copied()
+      .ok_or(Error::InvalidItemNumber)
+  }
+}
+
+impl fmt::Display for Ore {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl fmt::Display for Item {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name())
+  }
+}
+
+impl FromStr for Ore {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    Self::iter()
+      .find(|o| o.name() == s)
+      .ok_or(Error::InvalidItemName(s.to_string()))
+  }
+}
+
+impl FromStr for Item {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self> {
+    Self::iter()
+      .find(|i| i.name() == s)
+      .ok_or(Error::InvalidItemName(s.to_string()))
   }
 }
 
@@ -46,11 +140,6 @@ impl Random for Item {
   where
     Self: Sized,
   {
-    use Item::*;
-
-    match rng.random::<u8>() % ITEM_KINDS as u8 {
-      0 => Nourriture,
-      x => Ore(unsafe { self::Ore::try_from(x - 1).unwrap_unchecked() }),
-    }
+    Self::ALL[rng.random_range(0..ITEM_KINDS)]
   }
 }
@@ -0,0 +1,15 @@
+use {
+  crate::zappy::{Inventory, Position},
+  serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileView {
+  pub position: Position,
+  pub players: usize,
+  pub items: Inventory,
+}
+
+/// Tiles seen by a player, nearest row first and from left to right within each row.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Look(pub Vec<TileView>);
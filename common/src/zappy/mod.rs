@@ -1,10 +1,23 @@
+mod config;
+mod density;
+mod egg;
+mod elevation;
 mod errors;
+mod event;
+mod inventory;
 mod item;
+mod look;
 mod player;
+mod position;
+mod snapshot;
+mod state;
 mod team;
 mod tile;
 mod world;
 
 pub mod constants;
 
-pub use {errors::*, item::*, player::*, team::*, tile::*, world::*};
+pub use {
+  config::*, density::*, egg::*, elevation::*, errors::*, event::*, inventory::*, item::*, look::*,
+  player::*, position::*, snapshot::*, state::*, team::*, tile::*, world::*,
+};
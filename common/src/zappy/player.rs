@@ -1,22 +1,105 @@
 use {
-  crate::zappy::{Item, Result},
-  std::collections::HashMap,
+  crate::zappy::{
+    Error, Inventory, Item, Orientation, Position, Result,
+    constants::{FOOD_TIME_UNITS, INITIAL_FOOD, MAX_LEVEL},
+  },
+  serde::{Deserialize, Serialize},
 };
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Player {
+  id: usize,
+  team: String,
+  position: Position,
+  orientation: Orientation,
   level: u8,
-  inventory: HashMap<Item, usize>,
+  life: u64,
+  inventory: Inventory,
 }
 
 impl Player {
-  pub fn new() -> Self {
+  pub(crate) fn new(
+    id: usize,
+    team: impl Into<String>,
+    position: Position,
+    orientation: Orientation,
+  ) -> Self {
     Player {
-      level: 0,
-      inventory: HashMap::new(),
+      id,
+      team: team.into(),
+      position,
+      orientation,
+      level: 1,
+      life: 0,
+      inventory: {
+        let mut inventory = Inventory::new();
+        inventory.add(Item::Nourriture, INITIAL_FOOD);
+        inventory
+      },
     }
   }
 
+  pub fn id(&self) -> usize {
+    self.id
+  }
+
+  pub fn team(&self) -> &str {
+    &self.team
+  }
+
+  pub fn position(&self) -> Position {
+    self.position
+  }
+
+  pub(crate) fn set_position(&mut self, position: Position) {
+    self.position = position;
+  }
+
+  pub fn orientation(&self) -> Orientation {
+    self.orientation
+  }
+
+  pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+    self.orientation = orientation;
+  }
+
+  pub fn level(&self) -> u8 {
+    self.level
+  }
+
+  /// Time units left before the player has to eat again.
+  pub fn life(&self) -> u64 {
+    self.life
+  }
+
+  /// Drains `time_units` of life, eating from the inventory whenever it runs out. Returns
+  /// whether the player survived.
+  pub(crate) fn starve(&mut self, mut time_units: u64) -> bool {
+    while time_units > self.life {
+      time_units -= self.life;
+      self.life = 0;
+      if !self.inventory.take(Item::Nourriture, 1) {
+        return false;
+      }
+      self.life = FOOD_TIME_UNITS;
+    }
+    self.life -= time_units;
+    true
+  }
+
+  pub fn inventory(&self) -> &Inventory {
+    &self.inventory
+  }
+
+  pub(crate) fn inventory_mut(&mut self) -> &mut Inventory {
+    &mut self.inventory
+  }
+
   pub fn level_up(&mut self) -> Result<()> {
+    if self.level >= MAX_LEVEL {
+      return Err(Error::MaxLevelReached(self.id));
+    }
+    self.level += 1;
     Ok(())
   }
 }
@@ -0,0 +1,131 @@
+use {
+  crate::utils::Random,
+  rand::{Rng, RngExt},
+  serde::{Deserialize, Serialize},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Orientation {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Orientation {
+  pub fn left(self) -> Self {
+    use Orientation::*;
+    match self {
+      North => West,
+      West => South,
+      South => East,
+      East => North,
+    }
+  }
+
+  pub fn right(self) -> Self {
+    self.left().opposite()
+  }
+
+  pub fn opposite(self) -> Self {
+    self.left().left()
+  }
+
+  /// Unit step in map coordinates, `y` growing towards the south.
+  pub fn delta(self) -> (isize, isize) {
+    use Orientation::*;
+    match self {
+      North => (0, -1),
+      East => (1, 0),
+      South => (0, 1),
+      West => (-1, 0),
+    }
+  }
+}
+
+impl Random for Orientation {
+  fn random(rng: &mut impl Rng) -> Self
+  where
+    Self: Sized,
+  {
+    use Orientation::*;
+
+    match rng.random::<u8>() % 4 {
+      0 => North,
+      1 => East,
+      2 => South,
+      _ => West,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Position {
+  pub x: usize,
+  pub y: usize,
+}
+
+impl Position {
+  pub fn new(x: usize, y: usize) -> Self {
+    Self { x, y }
+  }
+
+  pub fn wrapping(x: isize, y: isize, width: usize, height: usize) -> Self {
+    Self {
+      x: x.rem_euclid(width as isize) as usize,
+      y: y.rem_euclid(height as isize) as usize,
+    }
+  }
+
+  pub fn offset(self, dx: isize, dy: isize, width: usize, height: usize) -> Self {
+    Self::wrapping(self.x as isize + dx, self.y as isize + dy, width, height)
+  }
+
+  pub fn forward(self, orientation: Orientation, width: usize, height: usize) -> Self {
+    let (dx, dy) = orientation.delta();
+    self.offset(dx, dy, width, height)
+  }
+
+  /// Shortest `(dx, dy)` leading from `self` to `to` on the torus. When both ways around are
+  /// equally long, the displacement that doesn't cross the map edge wins.
+  pub fn displacement(self, to: Position, width: usize, height: usize) -> (isize, isize) {
+    (
+      shortest_axis(self.x, to.x, width),
+      shortest_axis(self.y, to.y, height),
+    )
+  }
+
+  /// Direction `K` from which a sound emitted at `source` reaches a player standing on `self`
+  /// and facing `facing`: 0 on the same tile, otherwise 1 straight ahead then counterclockwise
+  /// up to 8 ahead on the right.
+  pub fn direction_from(
+    self,
+    source: Position,
+    facing: Orientation,
+    width: usize,
+    height: usize,
+  ) -> u8 {
+    let (dx, dy) = self.displacement(source, width, height);
+    if (dx, dy) == (0, 0) {
+      return 0;
+    }
+    let (fx, fy) = facing.delta();
+    let (lx, ly) = facing.left().delta();
+    let ahead = (dx * fx + dy * fy) as f64;
+    let left = (dx * lx + dy * ly) as f64;
+    let sector = (left.atan2(ahead).to_degrees() / 45.).round() as i64;
+    sector.rem_euclid(8) as u8 + 1
+  }
+}
+
+fn shortest_axis(from: usize, to: usize, size: usize) -> isize {
+  let direct = to as isize - from as isize;
+  let size = size as isize;
+  if direct * 2 > size {
+    direct - size
+  } else if direct * 2 < -size {
+    direct + size
+  } else {
+    direct
+  }
+}
@@ -0,0 +1,65 @@
+use {
+  crate::zappy::{Error, Result, SavedWorld},
+  bincode::config,
+  serde::{Deserialize, Serialize},
+};
+
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+  version: u32,
+  world: SavedWorld,
+}
+
+/// Leading field of every snapshot, read on its own so that an outdated dump is reported as
+/// such instead of as a decoding failure.
+#[derive(Deserialize)]
+struct Header {
+  version: u32,
+}
+
+impl Snapshot {
+  pub(crate) fn new(world: SavedWorld) -> Self {
+    Self {
+      version: SNAPSHOT_VERSION,
+      world,
+    }
+  }
+
+  pub fn version(&self) -> u32 {
+    self.version
+  }
+
+  pub(crate) fn into_world(self) -> Result<SavedWorld> {
+    check_version(self.version)?;
+    Ok(self.world)
+  }
+
+  pub fn to_json(&self) -> Result<String> {
+    Ok(serde_json::to_string_pretty(self)?)
+  }
+
+  pub fn from_json(json: &str) -> Result<Self> {
+    check_version(serde_json::from_str::<Header>(json)?.version)?;
+    Ok(serde_json::from_str(json)?)
+  }
+
+  pub fn to_bincode(&self) -> Result<Vec<u8>> {
+    Ok(bincode::serde::encode_to_vec(self, config::standard())?)
+  }
+
+  pub fn from_bincode(bytes: &[u8]) -> Result<Self> {
+    let (header, _) = bincode::serde::decode_from_slice::<Header, _>(bytes, config::standard())?;
+    check_version(header.version)?;
+    Ok(bincode::serde::decode_from_slice(bytes, config::standard())?.0)
+  }
+}
+
+fn check_version(version: u32) -> Result<()> {
+  if version == SNAPSHOT_VERSION {
+    Ok(())
+  } else {
+    Err(Error::UnsupportedSnapshotVersion(version))
+  }
+}
@@ -1,41 +1,77 @@
-use crate::zappy::{Error, Player, Result, constants::TEAM_SIZE};
+use {
+  crate::zappy::{Error, Result},
+  rand::{Rng, RngExt},
+  serde::{Deserialize, Serialize},
+  std::collections::BTreeSet,
+};
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Team {
   name: String,
-  players: [Option<Player>; TEAM_SIZE],
+  capacity: usize,
+  players: BTreeSet<usize>,
+  eggs: Vec<usize>,
 }
 
 impl Team {
-  pub(crate) fn empty(name: impl Into<String>) -> Self {
+  pub(crate) fn empty(name: impl Into<String>, capacity: usize) -> Self {
     Team {
       name: name.into(),
-      players: [(); TEAM_SIZE].map(|()| None),
+      capacity,
+      players: BTreeSet::new(),
+      eggs: Vec::new(),
     }
   }
 
-  pub(crate) fn add_player(&mut self) -> Result<usize> {
-    for (i, p) in self.players.iter_mut().enumerate() {
-      if p.is_none() {
-        *p = Some(Player::new());
-        return Ok(i);
-      }
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Number of slots the team starts with, before any egg is laid.
+  pub fn capacity(&self) -> usize {
+    self.capacity
+  }
+
+  pub fn players(&self) -> impl Iterator<Item = usize> {
+    self.players.iter().copied()
+  }
+
+  pub fn eggs(&self) -> impl Iterator<Item = usize> {
+    self.eggs.iter().copied()
+  }
+
+  pub fn free_slots(&self) -> usize {
+    self.eggs.len()
+  }
+
+  pub(crate) fn add_egg(&mut self, id: usize) {
+    self.eggs.push(id);
+  }
+
+  pub(crate) fn remove_egg(&mut self, id: usize) -> bool {
+    self
+      .eggs
+      .iter()
+      .position(|&e| e == id)
+      .map(|i| self.eggs.swap_remove(i))
+      .is_some()
+  }
+
+  /// Hatches one of the team's eggs at random for player `id`, returning the egg's id.
+  pub(crate) fn add_player(&mut self, rng: &mut impl Rng, id: usize) -> Result<usize> {
+    if self.eggs.is_empty() {
+      return Err(Error::TeamIsFull(self.name.to_string()));
     }
-    Err(Error::TeamIsFull(self.name.to_string()))
+    let egg = self.eggs.swap_remove(rng.random_range(0..self.eggs.len()));
+    self.players.insert(id);
+    Ok(egg)
   }
 
   pub(crate) fn remove_player(&mut self, id: usize) -> Result<()> {
-    use Error::*;
-    self
-      .players
-      .get_mut(id)
-      .ok_or(PlayerOutOfBounds(self.name.to_string(), id))
-      .and_then(|o| {
-        if o.is_none() {
-          Err(PlayerNotFound(self.name.to_string(), id))
-        } else {
-          *o = None;
-          Ok(())
-        }
-      })
+    if self.players.remove(&id) {
+      Ok(())
+    } else {
+      Err(Error::PlayerNotFound(self.name.to_string(), id))
+    }
   }
 }
@@ -1,18 +1,35 @@
-use rand::Rng;
+use {
+  crate::zappy::{Inventory, Item},
+  serde::{Deserialize, Serialize},
+};
 
-use crate::{utils::Random, zappy::Item};
-
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct Tile {
-  content: Option<Item>,
+  items: Inventory,
 }
 
 impl Tile {
-  pub fn has_item(&self) -> bool {
-    self.content.is_some()
+  pub fn items(&self) -> &Inventory {
+    &self.items
+  }
+
+  pub fn count(&self, item: Item) -> usize {
+    self.items.count(item)
+  }
+
+  pub fn weight(&self) -> usize {
+    self.items.weight()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.items.is_empty()
+  }
+
+  pub fn add(&mut self, item: Item, amount: usize) {
+    self.items.add(item, amount);
   }
 
-  pub fn fill_randomly(&mut self, rng: &mut impl Rng) {
-    self.content = Some(Item::random(rng));
+  pub fn take(&mut self, item: Item, amount: usize) -> bool {
+    self.items.take(item, amount)
   }
 }
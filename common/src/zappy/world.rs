@@ -1,43 +1,316 @@
 use {
-  crate::zappy::{Error, Player, Result, Team, Tile},
-  rand::{Rng, RngExt},
-  std::collections::{HashMap, LinkedList},
+  crate::{
+    utils::{Random, SeededRng},
+    zappy::{
+      Egg, ElevationRequirement, Error, Event, GameState, Incantation, Item, Look, Ore,
+      Orientation, Player, Position, ResourceDensity, Result, Snapshot, Team, Tile, TileView,
+      WorldConfig,
+      constants::{DEFAULT_TEAM_CAPACITY, MAX_LEVEL, RESPAWN_INTERVAL, WINNING_PLAYERS},
+      elevation_requirement,
+    },
+  },
+  rand::RngExt,
+  serde::{Deserialize, Serialize},
+  std::collections::{BTreeMap, BTreeSet},
 };
 
-pub struct World<'a> {
+#[derive(Clone)]
+pub struct World {
   x: usize,
   y: usize,
   tiles: Vec<Tile>,
-  teams: HashMap<String, Team>,
-  player_positions: HashMap<(usize, usize), LinkedList<&'a mut Player>>,
+  teams: BTreeMap<String, Team>,
+  team_capacity: usize,
+  seed: u64,
+  rng: SeededRng,
+  frequency: u64,
+  density: ResourceDensity,
+  respawn_clock: u64,
+  players: BTreeMap<usize, Player>,
+  /// Players standing on each tile, rebuilt from `players` on restore.
+  player_positions: Vec<BTreeSet<usize>>,
+  next_player_id: usize,
+  incantations: BTreeMap<usize, Incantation>,
+  eggs: BTreeMap<usize, Egg>,
+  next_egg_id: usize,
+  state: GameState,
 }
 
-impl<'a> World<'a> {
+/// What a snapshot stores of a world. It only becomes a `World` again through `World::restore`,
+/// which checks it and rebuilds the per-tile index.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct SavedWorld {
+  x: usize,
+  y: usize,
+  tiles: Vec<Tile>,
+  teams: BTreeMap<String, Team>,
+  team_capacity: usize,
+  seed: u64,
+  rng: SeededRng,
+  frequency: u64,
+  density: ResourceDensity,
+  respawn_clock: u64,
+  players: BTreeMap<usize, Player>,
+  next_player_id: usize,
+  incantations: BTreeMap<usize, Incantation>,
+  eggs: BTreeMap<usize, Egg>,
+  next_egg_id: usize,
+  state: GameState,
+}
+
+impl World {
   pub fn empty(x: usize, y: usize) -> Self {
     Self {
       x,
       y,
       tiles: vec![Tile::default(); x * y],
-      teams: HashMap::new(),
-      player_positions: HashMap::new(),
+      teams: BTreeMap::new(),
+      team_capacity: DEFAULT_TEAM_CAPACITY,
+      seed: 0,
+      rng: SeededRng::seed_from_u64(0),
+      frequency: WorldConfig::default().frequency,
+      density: ResourceDensity::default(),
+      respawn_clock: 0,
+      players: BTreeMap::new(),
+      player_positions: vec![BTreeSet::new(); x * y],
+      next_player_id: 0,
+      incantations: BTreeMap::new(),
+      eggs: BTreeMap::new(),
+      next_egg_id: 0,
+      state: GameState::Running,
     }
   }
 
-  pub fn generate(rng: &mut impl Rng, x: usize, y: usize) -> Self {
-    let mut instance = Self::empty(x, y);
-    let ore_count = x * y / 20;
+  /// Builds the starting map of `config`. The same configuration, seed included, always yields
+  /// the same map, egg placement and respawn sequence.
+  pub fn new(config: WorldConfig) -> Result<Self> {
+    use Error::*;
+    if config
+      .width
+      .checked_mul(config.height)
+      .is_none_or(|area| area == 0)
+    {
+      return Err(InvalidMapSize(config.width, config.height));
+    }
+    if config.frequency == 0 {
+      return Err(InvalidFrequency(config.frequency));
+    }
 
-    let mut i = 0;
-    while i < ore_count {
-      let j = rng.random::<u64>() as usize % instance.tiles.len();
-      if instance.tiles[j].has_item() {
-        continue;
+    let mut instance = Self::empty(config.width, config.height);
+    instance.seed = config.seed;
+    instance.rng = SeededRng::seed_from_u64(config.seed);
+    instance.frequency = config.frequency;
+    instance.density = config.density;
+    instance.team_capacity = config.clients_per_team;
+    instance.spawn_resources();
+    for name in config.teams {
+      instance.add_team(name)?;
+    }
+    Ok(instance)
+  }
+
+  pub fn snapshot(&self) -> Snapshot {
+    let world = self.clone();
+    Snapshot::new(SavedWorld {
+      x: world.x,
+      y: world.y,
+      tiles: world.tiles,
+      teams: world.teams,
+      team_capacity: world.team_capacity,
+      seed: world.seed,
+      rng: world.rng,
+      frequency: world.frequency,
+      density: world.density,
+      respawn_clock: world.respawn_clock,
+      players: world.players,
+      next_player_id: world.next_player_id,
+      incantations: world.incantations,
+      eggs: world.eggs,
+      next_egg_id: world.next_egg_id,
+      state: world.state,
+    })
+  }
+
+  pub fn restore(snapshot: Snapshot) -> Result<Self> {
+    use Error::*;
+    let SavedWorld {
+      x,
+      y,
+      tiles,
+      teams,
+      team_capacity,
+      seed,
+      rng,
+      frequency,
+      density,
+      respawn_clock,
+      players,
+      next_player_id,
+      incantations,
+      eggs,
+      next_egg_id,
+      state,
+    } = snapshot.into_world()?;
+    let mut instance = Self {
+      x,
+      y,
+      tiles,
+      teams,
+      team_capacity,
+      seed,
+      rng,
+      frequency,
+      density,
+      respawn_clock,
+      players,
+      player_positions: Vec::new(),
+      next_player_id,
+      incantations,
+      eggs,
+      next_egg_id,
+      state,
+    };
+    let area = instance.x.checked_mul(instance.y);
+    if instance.tiles.is_empty() || area != Some(instance.tiles.len()) {
+      return Err(InvalidSnapshot(format!(
+        "{} tiles for a {}x{} map",
+        instance.tiles.len(),
+        instance.x,
+        instance.y
+      )));
+    }
+
+    if instance.frequency == 0 {
+      return Err(InvalidFrequency(instance.frequency));
+    }
+    instance.check_entities().map_err(InvalidSnapshot)?;
+
+    instance.player_positions = vec![BTreeSet::new(); instance.tiles.len()];
+    for player in instance.players.values() {
+      let position = player.position();
+      let i = instance.tile_index(position.x, position.y);
+      instance.player_positions[i].insert(player.id());
+    }
+    Ok(instance)
+  }
+
+  /// Checks that a restored world only refers to players, eggs and teams it holds, and only to
+  /// positions on its map, describing the first inconsistency found.
+  fn check_entities(&self) -> std::result::Result<(), String> {
+    let on_map = |p: Position| p.x < self.x && p.y < self.y;
+    let in_team = |team: &str| self.teams.get(team).ok_or(format!("no team '{team}'"));
+
+    for (&id, player) in &self.players {
+      if player.id() != id || id >= self.next_player_id {
+        return Err(format!("player #{id} has an invalid id"));
+      }
+      if !on_map(player.position()) {
+        return Err(format!("player #{id} is out of the map"));
+      }
+      if !in_team(player.team())?.players().any(|p| p == id) {
+        return Err(format!("player #{id} is missing from its team"));
+      }
+    }
+    for (&id, egg) in &self.eggs {
+      if egg.id() != id || id >= self.next_egg_id {
+        return Err(format!("egg #{id} has an invalid id"));
+      }
+      if !on_map(egg.position()) {
+        return Err(format!("egg #{id} is out of the map"));
+      }
+      if !in_team(egg.team())?.eggs().any(|e| e == id) {
+        return Err(format!("egg #{id} is missing from its team"));
       }
-      instance.tiles[j].fill_randomly(rng);
-      i += 1;
     }
+    for (name, team) in &self.teams {
+      if team.name() != name {
+        return Err(format!("team '{name}' is stored as '{}'", team.name()));
+      }
+      if let Some(id) = team
+        .players()
+        .find(|id| self.players.get(id).is_none_or(|p| p.team() != name))
+      {
+        return Err(format!(
+          "team '{name}' lists player #{id}, not one of its own"
+        ));
+      }
+      if let Some(id) = team
+        .eggs()
+        .find(|id| self.eggs.get(id).is_none_or(|e| e.team() != name))
+      {
+        return Err(format!("team '{name}' lists egg #{id}, not one of its own"));
+      }
+    }
+    for (&leader, incantation) in &self.incantations {
+      if !on_map(incantation.position()) {
+        return Err(format!("incantation of player #{leader} is out of the map"));
+      }
+      if let Some(id) = std::iter::once(&leader)
+        .chain(incantation.participants())
+        .find(|id| !self.players.contains_key(id))
+      {
+        return Err(format!(
+          "incantation of player #{leader} refers to player #{id}"
+        ));
+      }
+    }
+    Ok(())
+  }
 
-    instance
+  pub fn seed(&self) -> u64 {
+    self.seed
+  }
+
+  pub fn frequency(&self) -> u64 {
+    self.frequency
+  }
+
+  pub fn set_frequency(&mut self, frequency: u64) -> Result<()> {
+    if frequency == 0 {
+      return Err(Error::InvalidFrequency(frequency));
+    }
+    self.frequency = frequency;
+    Ok(())
+  }
+
+  /// Tops every resource up to its density, returning the tiles that received some.
+  fn spawn_resources(&mut self) -> Vec<Position> {
+    let area = self.tiles.len();
+    let mut touched = BTreeSet::new();
+    if area == 0 {
+      return Vec::new();
+    }
+    for item in Item::iter() {
+      let target = self.density.quantity(item, area);
+      let current = self.item_count(item);
+      for _ in current..target {
+        let i = self.rng.random_range(0..area);
+        self.tiles[i].add(item, 1);
+        touched.insert(i);
+      }
+    }
+    touched
+      .into_iter()
+      .map(|i| Position::new(i % self.x, i / self.x))
+      .collect()
+  }
+
+  fn update_resources(&mut self, time_units: u64) -> Option<Event> {
+    self.respawn_clock += time_units;
+    if self.respawn_clock < RESPAWN_INTERVAL {
+      return None;
+    }
+    self.respawn_clock %= RESPAWN_INTERVAL;
+    let positions = self.spawn_resources();
+    (!positions.is_empty()).then_some(Event::ResourcesSpawned { positions })
+  }
+
+  pub fn item_count(&self, item: Item) -> usize {
+    self.tiles.iter().map(|t| t.count(item)).sum()
+  }
+
+  pub fn density(&self) -> &ResourceDensity {
+    &self.density
   }
 
   pub fn x(&self) -> usize {
@@ -48,93 +321,500 @@ impl<'a> World<'a> {
     self.y
   }
 
-  pub fn tile_at_pos(&'a self, x: usize, y: usize) -> &'a Tile {
-    &self.tiles[y * self.x + x]
+  fn tile_index(&self, x: usize, y: usize) -> usize {
+    (y % self.y) * self.x + x % self.x
   }
 
-  pub fn tile_at_pos_mut(&'a mut self, x: usize, y: usize) -> &'a mut Tile {
-    &mut self.tiles[y * self.x + x]
+  pub fn wrap(&self, x: isize, y: isize) -> Position {
+    Position::wrapping(x, y, self.x, self.y)
   }
 
-  pub fn tile_at_index(&'a self, i: usize) -> &'a Tile {
+  pub fn displacement(&self, from: Position, to: Position) -> (isize, isize) {
+    from.displacement(to, self.x, self.y)
+  }
+
+  pub fn tile_at_pos(&self, x: usize, y: usize) -> &Tile {
+    &self.tiles[self.tile_index(x, y)]
+  }
+
+  pub fn tile_at_pos_mut(&mut self, x: usize, y: usize) -> &mut Tile {
+    let i = self.tile_index(x, y);
+    &mut self.tiles[i]
+  }
+
+  pub fn tile_at_index(&self, i: usize) -> &Tile {
     &self.tiles[i]
   }
 
-  pub fn tile_at_index_mut(&'a mut self, i: usize) -> &'a mut Tile {
+  pub fn tile_at_index_mut(&mut self, i: usize) -> &mut Tile {
     &mut self.tiles[i]
   }
 
-  pub fn teams(&'a self) -> &'a HashMap<String, Team> {
+  pub fn teams(&self) -> &BTreeMap<String, Team> {
     &self.teams
   }
 
-  pub fn teams_mut(&'a mut self) -> &'a mut HashMap<String, Team> {
+  pub fn teams_mut(&mut self) -> &mut BTreeMap<String, Team> {
     &mut self.teams
   }
 
-  pub fn players_at_pos<'b>(
-    &'a self,
-    x: usize,
-    y: usize,
-  ) -> Option<impl Iterator<Item = &'a Player>>
-  where
-    'a: 'b,
-  {
-    self
-      .player_positions
-      .get(&(x, y))
-      .map(|v| v.iter().map(|v| &**v))
+  pub fn player(&self, id: usize) -> Result<&Player> {
+    self.players.get(&id).ok_or(Error::PlayerDoesntExist(id))
   }
 
-  pub fn player_at_pos_mut(
-    &'a mut self,
-    x: usize,
-    y: usize,
-  ) -> Option<impl Iterator<Item = &'a mut Player>> {
+  pub fn player_mut(&mut self, id: usize) -> Result<&mut Player> {
     self
-      .player_positions
-      .get_mut(&(x, y))
-      .map(|v| v.iter_mut().map(|v| &mut **v))
+      .players
+      .get_mut(&id)
+      .ok_or(Error::PlayerDoesntExist(id))
+  }
+
+  pub fn players(&self) -> impl Iterator<Item = &Player> {
+    self.players.values()
+  }
+
+  pub fn players_at_pos(&self, x: usize, y: usize) -> impl Iterator<Item = &Player> {
+    self.player_positions[self.tile_index(x, y)]
+      .iter()
+      .filter_map(|id| self.players.get(id))
+  }
+
+  /// Runs `f` on every player standing on the tile, looking up only those.
+  pub fn player_at_pos_mut(&mut self, x: usize, y: usize, mut f: impl FnMut(&mut Player)) {
+    let i = self.tile_index(x, y);
+    for id in &self.player_positions[i] {
+      if let Some(player) = self.players.get_mut(id) {
+        f(player);
+      }
+    }
+  }
+
+  pub(crate) fn move_player(&mut self, id: usize, position: Position) -> Result<()> {
+    let old = self.player(id)?.position();
+    let (old, new) = (
+      self.tile_index(old.x, old.y),
+      self.tile_index(position.x, position.y),
+    );
+    self.player_positions[old].remove(&id);
+    self.player_positions[new].insert(id);
+    self.player_mut(id)?.set_position(position);
+    Ok(())
+  }
+
+  pub fn forward(&mut self, id: usize) -> Result<Position> {
+    let player = self.player(id)?;
+    let position = player
+      .position()
+      .forward(player.orientation(), self.x, self.y);
+    self.move_player(id, position)?;
+    Ok(position)
+  }
+
+  pub fn turn_left(&mut self, id: usize) -> Result<Orientation> {
+    let player = self.player_mut(id)?;
+    let orientation = player.orientation().left();
+    player.set_orientation(orientation);
+    Ok(orientation)
+  }
+
+  pub fn turn_right(&mut self, id: usize) -> Result<Orientation> {
+    let player = self.player_mut(id)?;
+    let orientation = player.orientation().right();
+    player.set_orientation(orientation);
+    Ok(orientation)
+  }
+
+  /// Direction `K` in which player `to` hears a broadcast from player `from`.
+  pub fn broadcast_direction(&self, from: usize, to: usize) -> Result<u8> {
+    let (from, to) = (self.player(from)?, self.player(to)?);
+    Ok(
+      to.position()
+        .direction_from(from.position(), to.orientation(), self.x, self.y),
+    )
+  }
+
+  /// Vision cone of a player: one more row per level, each row two tiles wider than the last.
+  pub fn look(&self, id: usize) -> Result<Look> {
+    let player = self.player(id)?;
+    let (position, orientation) = (player.position(), player.orientation());
+    let (fx, fy) = orientation.delta();
+    let (rx, ry) = orientation.right().delta();
+
+    let mut tiles = Vec::new();
+    for distance in 0..=player.level() as isize {
+      for side in -distance..=distance {
+        let position = position.offset(
+          fx * distance + rx * side,
+          fy * distance + ry * side,
+          self.x,
+          self.y,
+        );
+        tiles.push(TileView {
+          position,
+          players: self.player_positions[self.tile_index(position.x, position.y)].len(),
+          items: *self.tile_at_pos(position.x, position.y).items(),
+        });
+      }
+    }
+    Ok(Look(tiles))
+  }
+
+  pub fn take(&mut self, id: usize, item: Item) -> Result<Event> {
+    let position = self.player(id)?.position();
+    if !self.tile_at_pos_mut(position.x, position.y).take(item, 1) {
+      return Err(Error::ItemNotOnTile(item));
+    }
+    self.player_mut(id)?.inventory_mut().add(item, 1);
+    Ok(Event::ItemTaken { id, item, position })
+  }
+
+  pub fn set(&mut self, id: usize, item: Item) -> Result<Event> {
+    let player = self.player_mut(id)?;
+    if !player.inventory_mut().take(item, 1) {
+      return Err(Error::ItemNotInInventory(item));
+    }
+    let position = player.position();
+    self.tile_at_pos_mut(position.x, position.y).add(item, 1);
+    Ok(Event::ItemDropped { id, item, position })
+  }
+
+  pub fn team_capacity(&self) -> usize {
+    self.team_capacity
+  }
+
+  /// Capacity given to the teams added from now on.
+  pub fn set_team_capacity(&mut self, capacity: usize) {
+    self.team_capacity = capacity;
   }
 
-  pub fn add_team(&'a mut self, name: impl Into<String> + AsRef<str>) -> Result<()> {
+  pub fn add_team(&mut self, name: impl Into<String> + AsRef<str>) -> Result<()> {
     let name = name.into();
+    if self.teams.contains_key(&name) {
+      return Err(Error::TeamExists(name));
+    }
+    let capacity = self.team_capacity;
     self
       .teams
-      .get_mut(name.as_str())
-      .map(|_| Err::<(), _>(Error::TeamExists(name.clone())))
-      .transpose()
-      .map(|_| {
-        self.teams.insert(name.clone(), Team::empty(name));
-      })
+      .insert(name.clone(), Team::empty(name.clone(), capacity));
+    for _ in 0..capacity {
+      let position = self.random_position();
+      self.add_egg(&name, position, None)?;
+    }
+    Ok(())
   }
 
-  pub fn remove_team(&'a mut self, name: impl Into<String> + AsRef<str>) -> Result<()> {
-    self
+  pub fn remove_team(&mut self, name: impl Into<String> + AsRef<str>) -> Result<()> {
+    let team = self
       .teams
       .remove(name.as_ref())
-      .map(|_| ())
-      .ok_or(Error::TeamDoesntExist(name.into()))
+      .ok_or(Error::TeamDoesntExist(name.into()))?;
+    for id in team.players() {
+      self.forget_player(id)?;
+    }
+    for id in team.eggs() {
+      self.eggs.remove(&id);
+    }
+    Ok(())
   }
 
-  pub fn add_player(&'a mut self, team_name: impl Into<String> + AsRef<str>) -> Result<usize> {
+  /// Hatches one of the team's eggs into a new player, returning the player's id and the egg's.
+  pub fn add_player(
+    &mut self,
+    team_name: impl Into<String> + AsRef<str>,
+  ) -> Result<(usize, usize)> {
     use Error::*;
-    self
+    let id = self.next_player_id;
+    let team = self
       .teams
       .get_mut(team_name.as_ref())
-      .map_or(Err(TeamDoesntExist(team_name.into())), |t| {
-        // TODO: add player to player_positions
-        Ok(t.add_player()?)
-      })
+      .ok_or(TeamDoesntExist(team_name.into()))?;
+    let egg = team.add_player(&mut self.rng, id)?;
+    let egg = self.eggs.remove(&egg).ok_or(EggDoesntExist(egg))?;
+
+    let position = egg.position();
+    let player = Player::new(id, egg.team(), position, Orientation::random(&mut self.rng));
+    let i = self.tile_index(position.x, position.y);
+    self.player_positions[i].insert(id);
+    self.players.insert(id, player);
+    self.next_player_id += 1;
+    Ok((id, egg.id()))
+  }
+
+  fn random_position(&mut self) -> Position {
+    Position::new(
+      self.rng.random_range(0..self.x),
+      self.rng.random_range(0..self.y),
+    )
+  }
+
+  pub fn egg(&self, id: usize) -> Result<&Egg> {
+    self.eggs.get(&id).ok_or(Error::EggDoesntExist(id))
+  }
+
+  pub fn eggs(&self) -> impl Iterator<Item = &Egg> {
+    self.eggs.values()
+  }
+
+  pub fn eggs_at_pos(&self, x: usize, y: usize) -> impl Iterator<Item = &Egg> {
+    let position = self.wrap(x as isize, y as isize);
+    self.eggs.values().filter(move |e| e.position() == position)
+  }
+
+  fn add_egg(
+    &mut self,
+    team_name: &str,
+    position: Position,
+    laid_by: Option<usize>,
+  ) -> Result<usize> {
+    let id = self.next_egg_id;
+    self
+      .teams
+      .get_mut(team_name)
+      .ok_or(Error::TeamDoesntExist(team_name.to_string()))?
+      .add_egg(id);
+    self
+      .eggs
+      .insert(id, Egg::new(id, team_name, position, laid_by));
+    self.next_egg_id += 1;
+    Ok(id)
+  }
+
+  /// Lays an egg on the player's tile, opening a new slot in its team.
+  pub fn lay_egg(&mut self, player_id: usize) -> Result<usize> {
+    let player = self.player(player_id)?;
+    let (team, position) = (player.team().to_string(), player.position());
+    self.add_egg(&team, position, Some(player_id))
+  }
+
+  pub fn destroy_egg(&mut self, id: usize) -> Result<Egg> {
+    let egg = self.eggs.remove(&id).ok_or(Error::EggDoesntExist(id))?;
+    if let Some(team) = self.teams.get_mut(egg.team()) {
+      team.remove_egg(id);
+    }
+    Ok(egg)
   }
 
-  pub fn remove_player(&'a mut self, team_name: String, id: usize) -> Result<()> {
+  /// Pushes every other player on the ejector's tile one tile forward and destroys the eggs
+  /// laid there.
+  pub fn eject(&mut self, id: usize) -> Result<Vec<Event>> {
+    let player = self.player(id)?;
+    let (from, orientation) = (player.position(), player.orientation());
+    let to = from.forward(orientation, self.x, self.y);
+
+    let victims = self
+      .players_at_pos(from.x, from.y)
+      .map(Player::id)
+      .filter(|&v| v != id)
+      .collect::<Vec<_>>();
+    let eggs = self
+      .eggs_at_pos(from.x, from.y)
+      .map(Egg::id)
+      .collect::<Vec<_>>();
+
+    let mut events = Vec::with_capacity(victims.len() + eggs.len());
+    for victim in victims {
+      self.move_player(victim, to)?;
+      let facing = self.player(victim)?.orientation();
+      events.push(Event::PlayerEjected {
+        id: victim,
+        from,
+        to,
+        direction: to.direction_from(from, facing, self.x, self.y),
+      });
+    }
+    for egg in eggs {
+      let egg = self.destroy_egg(egg)?;
+      events.push(Event::EggDestroyed {
+        id: egg.id(),
+        team: egg.team().to_string(),
+        position: egg.position(),
+      });
+    }
+    Ok(events)
+  }
+
+  pub fn free_slots(&self, team_name: impl Into<String> + AsRef<str>) -> Result<usize> {
+    self
+      .teams
+      .get(team_name.as_ref())
+      .map(Team::free_slots)
+      .ok_or(Error::TeamDoesntExist(team_name.into()))
+  }
+
+  pub fn remove_player(&mut self, id: usize) -> Result<Player> {
     use Error::*;
+    let team_name = self.player(id)?.team().to_string();
     self
       .teams
       .get_mut(&team_name)
-      .map_or(Err(TeamDoesntExist(team_name.clone())), |t| {
-        Ok(t.remove_player(id)?)
+      .ok_or(TeamDoesntExist(team_name.clone()))?
+      .remove_player(id)?;
+    self.forget_player(id)
+  }
+
+  fn forget_player(&mut self, id: usize) -> Result<Player> {
+    let player = self
+      .players
+      .remove(&id)
+      .ok_or(Error::PlayerDoesntExist(id))?;
+    let position = player.position();
+    let i = self.tile_index(position.x, position.y);
+    self.player_positions[i].remove(&id);
+    self.incantations.remove(&id);
+    Ok(player)
+  }
+
+  /// Lets `time_units` pass: respawns resources and removes the players that starved, along
+  /// with the incantations they were leading.
+  pub fn tick(&mut self, time_units: u64) -> Vec<Event> {
+    let mut events = Vec::from_iter(self.update_resources(time_units));
+    let dead = self
+      .players
+      .values_mut()
+      .filter_map(|p| (!p.starve(time_units)).then_some(p.id()))
+      .collect::<Vec<_>>();
+
+    for id in dead {
+      let incantation = self
+        .incantation(id)
+        .map(|i| (i.position(), i.participants().to_vec()));
+      let Ok(player) = self.remove_player(id) else {
+        continue;
+      };
+      events.push(Event::PlayerDied {
+        id,
+        team: player.team().to_string(),
+        position: player.position(),
+      });
+      if let Some((position, participants)) = incantation {
+        events.push(Event::IncantationAbandoned {
+          leader: id,
+          position,
+          participants,
+        });
+      }
+    }
+    events
+  }
+
+  pub fn incantation(&self, leader: usize) -> Option<&Incantation> {
+    self.incantations.get(&leader)
+  }
+
+  /// Keeps the `candidates` standing on `position` at `level`, checking there are enough of them
+  /// and enough stones on the tile.
+  fn elevation_participants(
+    &self,
+    position: Position,
+    level: u8,
+    requirement: &ElevationRequirement,
+    candidates: impl IntoIterator<Item = usize>,
+  ) -> Result<Vec<usize>> {
+    use Error::*;
+    let participants = candidates
+      .into_iter()
+      .filter(|id| {
+        self
+          .players
+          .get(id)
+          .is_some_and(|p| p.position() == position && p.level() == level)
       })
+      .collect::<Vec<_>>();
+    if participants.len() < requirement.players {
+      return Err(NotEnoughPlayers(requirement.players, participants.len()));
+    }
+
+    let tile = self.tile_at_pos(position.x, position.y);
+    for (ore, &required) in Ore::iter().zip(&requirement.stones) {
+      let found = tile.count(Item::Ore(ore));
+      if found < required {
+        return Err(NotEnoughStones(ore, required, found));
+      }
+    }
+    Ok(participants)
+  }
+
+  pub fn start_incantation(&mut self, leader: usize) -> Result<&Incantation> {
+    if self.incantations.contains_key(&leader) {
+      return Err(Error::AlreadyIncanting(leader));
+    }
+    let player = self.player(leader)?;
+    let (position, level) = (player.position(), player.level());
+    let requirement = elevation_requirement(level).ok_or(Error::MaxLevelReached(leader))?;
+    let on_tile = &self.player_positions[self.tile_index(position.x, position.y)];
+    let participants =
+      self.elevation_participants(position, level, requirement, on_tile.iter().copied())?;
+    Ok(
+      self
+        .incantations
+        .entry(leader)
+        .or_insert(Incantation::new(position, level, participants)),
+    )
+  }
+
+  /// Re-checks the elevation conditions, consumes the stones and promotes the participants still
+  /// standing on the incantation's tile at its level, returning them. Players who joined the tile
+  /// since it started don't take part. The incantation is over whatever the outcome.
+  pub fn finish_incantation(&mut self, leader: usize) -> Result<Vec<usize>> {
+    let incantation = self
+      .incantations
+      .remove(&leader)
+      .ok_or(Error::NoIncantation(leader))?;
+    let (position, level) = (incantation.position(), incantation.level());
+    let requirement = elevation_requirement(level).ok_or(Error::MaxLevelReached(leader))?;
+    let participants = self.elevation_participants(
+      position,
+      level,
+      requirement,
+      incantation.participants().iter().copied(),
+    )?;
+
+    let tile = self.tile_at_pos_mut(position.x, position.y);
+    for (ore, &required) in Ore::iter().zip(&requirement.stones) {
+      tile.take(Item::Ore(ore), required);
+    }
+    for &id in &participants {
+      self.player_mut(id)?.level_up()?;
+    }
+    self.update_state();
+    Ok(participants)
+  }
+
+  /// First team, by name, with enough players at the maximum level to win the game.
+  pub fn winner(&self) -> Option<&str> {
+    self.teams.keys().map(String::as_str).find(|&name| {
+      self
+        .players
+        .values()
+        .filter(|p| p.team() == name && p.level() >= MAX_LEVEL)
+        .count()
+        >= WINNING_PLAYERS
+    })
+  }
+
+  pub fn state(&self) -> &GameState {
+    &self.state
+  }
+
+  /// Ends the game as soon as a team wins; an ended game never starts again.
+  pub fn update_state(&mut self) -> &GameState {
+    if !matches!(self.state, GameState::Ended(_))
+      && let Some(winner) = self.winner()
+    {
+      self.state = GameState::Ended(winner.to_string());
+    }
+    &self.state
+  }
+
+  pub fn pause(&mut self) {
+    if self.state == GameState::Running {
+      self.state = GameState::Paused;
+    }
+  }
+
+  pub fn resume(&mut self) {
+    if self.state == GameState::Paused {
+      self.state = GameState::Running;
+    }
   }
 }
@@ -0,0 +1,100 @@
+use common::zappy::{Error, Orientation, Position, World};
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 10;
+
+fn check(cases: &[(Position, Orientation, Position, u8)]) {
+  for &(receiver, facing, source, expected) in cases {
+    assert_eq!(
+      receiver.direction_from(source, facing, WIDTH, HEIGHT),
+      expected,
+      "receiver {receiver:?} facing {facing:?}, source {source:?}"
+    );
+  }
+}
+
+#[test]
+fn every_direction_when_facing_north() {
+  use Orientation::*;
+  let receiver = Position::new(5, 5);
+  check(&[
+    (receiver, North, Position::new(5, 5), 0),
+    (receiver, North, Position::new(5, 3), 1),
+    (receiver, North, Position::new(4, 4), 2),
+    (receiver, North, Position::new(3, 5), 3),
+    (receiver, North, Position::new(4, 7), 4),
+    (receiver, North, Position::new(5, 8), 5),
+    (receiver, North, Position::new(7, 7), 6),
+    (receiver, North, Position::new(8, 5), 7),
+    (receiver, North, Position::new(6, 4), 8),
+  ]);
+}
+
+#[test]
+fn direction_is_relative_to_orientation() {
+  use Orientation::*;
+  let (receiver, north) = (Position::new(5, 5), Position::new(5, 3));
+  check(&[
+    (receiver, North, north, 1),
+    (receiver, East, north, 3),
+    (receiver, South, north, 5),
+    (receiver, West, north, 7),
+    (receiver, East, Position::new(6, 4), 2),
+    (receiver, South, Position::new(6, 4), 4),
+    (receiver, West, Position::new(6, 4), 6),
+  ]);
+}
+
+#[test]
+fn off_axis_sources_snap_to_nearest_direction() {
+  use Orientation::*;
+  let receiver = Position::new(5, 5);
+  check(&[
+    (receiver, North, Position::new(6, 1), 1),
+    (receiver, North, Position::new(6, 3), 8),
+    (receiver, North, Position::new(7, 4), 8),
+    (receiver, North, Position::new(8, 4), 7),
+    (receiver, North, Position::new(1, 6), 3),
+    (receiver, North, Position::new(2, 7), 4),
+  ]);
+}
+
+#[test]
+fn sound_takes_the_shortest_way_around_the_map() {
+  use Orientation::*;
+  let corner = Position::new(0, 0);
+  check(&[
+    (corner, North, Position::new(9, 0), 3),
+    (corner, North, Position::new(0, 9), 1),
+    (corner, North, Position::new(9, 9), 2),
+    (corner, North, Position::new(1, 1), 6),
+    (Position::new(9, 9), North, corner, 6),
+    (Position::new(9, 5), East, Position::new(1, 5), 1),
+  ]);
+}
+
+#[test]
+fn ties_keep_the_path_that_doesnt_wrap() {
+  use Orientation::*;
+  check(&[
+    (Position::new(0, 0), North, Position::new(5, 0), 7),
+    (Position::new(5, 0), North, Position::new(0, 0), 3),
+    (Position::new(0, 0), North, Position::new(0, 5), 5),
+    (Position::new(0, 5), North, Position::new(0, 0), 1),
+    (Position::new(0, 0), North, Position::new(5, 5), 6),
+  ]);
+}
+
+#[test]
+fn world_reports_same_tile_and_unknown_players() {
+  let mut world = World::empty(1, 1);
+  world.add_team("team").unwrap();
+  let first = world.add_player("team").unwrap().0;
+  let second = world.add_player("team").unwrap().0;
+
+  assert_eq!(world.broadcast_direction(first, second).unwrap(), 0);
+  assert!(matches!(
+    world.broadcast_direction(first, 42),
+    Err(Error::PlayerDoesntExist(42))
+  ));
+}
@@ -0,0 +1,143 @@
+use common::zappy::{Event, Orientation, Position, World, WorldConfig};
+
+fn world() -> World {
+  World::new(WorldConfig {
+    width: 5,
+    height: 4,
+    teams: vec!["red".into(), "blue".into()],
+    ..Default::default()
+  })
+  .unwrap()
+}
+
+fn face(world: &mut World, id: usize, orientation: Orientation) {
+  while world.player(id).unwrap().orientation() != orientation {
+    world.turn_right(id).unwrap();
+  }
+}
+
+/// Walks a player to `position`, then turns it towards `orientation`.
+fn place(world: &mut World, id: usize, position: Position, orientation: Orientation) {
+  face(world, id, Orientation::East);
+  while world.player(id).unwrap().position().x != position.x {
+    world.forward(id).unwrap();
+  }
+  face(world, id, Orientation::South);
+  while world.player(id).unwrap().position().y != position.y {
+    world.forward(id).unwrap();
+  }
+  face(world, id, orientation);
+}
+
+fn position(world: &World, id: usize) -> Position {
+  world.player(id).unwrap().position()
+}
+
+#[test]
+fn eject_pushes_players_and_destroys_eggs() {
+  use Orientation::*;
+  let mut world = world();
+  let (from, to) = (Position::new(2, 2), Position::new(3, 2));
+  let ejector = world.add_player("red").unwrap().0;
+  place(&mut world, ejector, from, East);
+  let bystander = world.add_player("blue").unwrap().0;
+  place(&mut world, bystander, to, North);
+
+  // Each victim hears the push from the tile it came from, relative to where it faces.
+  let mut victims = Vec::new();
+  for (team, facing, direction) in [
+    ("blue", East, 5),
+    ("red", West, 1),
+    ("blue", North, 3),
+    ("red", South, 7),
+  ] {
+    let id = world.add_player(team).unwrap().0;
+    place(&mut world, id, from, facing);
+    victims.push((id, direction));
+  }
+  world.lay_egg(victims[0].0).unwrap();
+  let elsewhere = world.lay_egg(bystander).unwrap();
+  let mut eggs = world
+    .eggs_at_pos(from.x, from.y)
+    .map(|egg| (egg.id(), egg.team().to_string()))
+    .collect::<Vec<_>>();
+  assert!(!eggs.is_empty());
+
+  let events = world.eject(ejector).unwrap();
+  let mut ejected = Vec::new();
+  let mut destroyed = Vec::new();
+  for event in events {
+    match event {
+      Event::PlayerEjected {
+        id,
+        from: f,
+        to: t,
+        direction,
+      } => {
+        assert_eq!((f, t), (from, to));
+        ejected.push((id, direction));
+      }
+      Event::EggDestroyed { id, team, position } => {
+        assert_eq!(position, from);
+        destroyed.push((id, team));
+      }
+      event => panic!("unexpected {event:?}"),
+    }
+  }
+  ejected.sort();
+  destroyed.sort();
+  eggs.sort();
+  assert_eq!(ejected, victims);
+  assert_eq!(destroyed, eggs);
+
+  assert_eq!(
+    (
+      position(&world, ejector),
+      world.player(ejector).unwrap().orientation()
+    ),
+    (from, East)
+  );
+  assert_eq!(position(&world, bystander), to);
+  for (id, _) in victims {
+    assert_eq!(position(&world, id), to);
+  }
+  assert_eq!(world.players_at_pos(to.x, to.y).count(), 5);
+  assert_eq!(world.players_at_pos(from.x, from.y).count(), 1);
+  for (id, team) in destroyed {
+    assert!(world.egg(id).is_err());
+    assert!(world.teams()[&team].eggs().all(|egg| egg != id));
+  }
+  assert!(world.egg(elsewhere).is_ok());
+}
+
+#[test]
+fn eject_wraps_around_the_map() {
+  let mut world = world();
+  let (ejector, victim) = (
+    world.add_player("red").unwrap().0,
+    world.add_player("red").unwrap().0,
+  );
+  place(&mut world, ejector, Position::new(4, 0), Orientation::North);
+  place(&mut world, victim, Position::new(4, 0), Orientation::West);
+  let events = world.eject(ejector).unwrap();
+  assert!(events.contains(&Event::PlayerEjected {
+    id: victim,
+    from: Position::new(4, 0),
+    to: Position::new(4, 3),
+    direction: 3,
+  }));
+  assert_eq!(position(&world, victim), Position::new(4, 3));
+}
+
+#[test]
+fn lonely_eject_does_nothing() {
+  let mut world = world();
+  let ejector = world.add_player("red").unwrap().0;
+  place(&mut world, ejector, Position::new(1, 1), Orientation::South);
+  let other = world.add_player("red").unwrap().0;
+  place(&mut world, other, Position::new(1, 2), Orientation::South);
+  let eggs = world.eggs_at_pos(1, 1).count();
+  let events = world.eject(ejector).unwrap();
+  assert_eq!(events.len(), eggs);
+  assert_eq!(position(&world, other), Position::new(1, 2));
+}
@@ -0,0 +1,173 @@
+use common::zappy::{
+  Error, Item, Ore, World, WorldConfig,
+  constants::{MAX_LEVEL, ORE_KINDS},
+  elevation_requirement,
+};
+
+/// A single-tile map, so that every player hatches on the same tile, with no stones on it.
+fn world() -> World {
+  let mut world = World::new(WorldConfig {
+    width: 1,
+    height: 1,
+    teams: vec!["red".into()],
+    clients_per_team: 8,
+    ..Default::default()
+  })
+  .unwrap();
+  set_stones(&mut world, [0; ORE_KINDS]);
+  world
+}
+
+fn set_stones(world: &mut World, stones: [usize; ORE_KINDS]) {
+  let tile = world.tile_at_pos_mut(0, 0);
+  for (ore, count) in Ore::iter().zip(stones) {
+    let item = Item::Ore(ore);
+    tile.take(item, tile.count(item));
+    tile.add(item, count);
+  }
+}
+
+fn stones(world: &World) -> [usize; ORE_KINDS] {
+  Ore::ALL.map(|ore| world.tile_at_pos(0, 0).count(Item::Ore(ore)))
+}
+
+/// Hatches a player and raises it to `level`.
+fn player(world: &mut World, level: u8) -> usize {
+  let id = world.add_player("red").unwrap().0;
+  for _ in 1..level {
+    world.player_mut(id).unwrap().level_up().unwrap();
+  }
+  id
+}
+
+fn level(world: &World, id: usize) -> u8 {
+  world.player(id).unwrap().level()
+}
+
+#[test]
+fn requirements_table() {
+  let table = (1..MAX_LEVEL)
+    .map(|level| {
+      let requirement = elevation_requirement(level).unwrap();
+      (requirement.players, requirement.stones)
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(
+    table,
+    [
+      (1, [1, 0, 0, 0, 0, 0]),
+      (2, [1, 1, 1, 0, 0, 0]),
+      (2, [2, 0, 1, 0, 2, 0]),
+      (4, [1, 1, 2, 0, 1, 0]),
+      (4, [1, 2, 1, 3, 0, 0]),
+      (6, [1, 2, 3, 0, 1, 0]),
+      (6, [2, 2, 2, 2, 2, 1]),
+    ]
+  );
+  assert!(elevation_requirement(0).is_none());
+  assert!(elevation_requirement(MAX_LEVEL).is_none());
+}
+
+#[test]
+fn successful_elevation_consumes_the_stones() {
+  let mut world = world();
+  let (leader, other, higher) = (
+    player(&mut world, 3),
+    player(&mut world, 3),
+    player(&mut world, 4),
+  );
+  set_stones(&mut world, [3, 1, 1, 0, 2, 1]);
+
+  let incantation = world.start_incantation(leader).unwrap();
+  assert_eq!(
+    (incantation.level(), incantation.participants()),
+    (3, &[leader, other][..])
+  );
+  assert_eq!(stones(&world), [3, 1, 1, 0, 2, 1]);
+
+  assert_eq!(world.finish_incantation(leader).unwrap(), [leader, other]);
+  assert_eq!(stones(&world), [1, 1, 0, 0, 0, 1]);
+  assert_eq!(
+    [leader, other, higher].map(|id| level(&world, id)),
+    [4, 4, 4]
+  );
+  assert!(world.incantation(leader).is_none());
+}
+
+#[test]
+fn start_checks_players_and_stones() {
+  let mut world = world();
+  let leader = player(&mut world, 2);
+  player(&mut world, 1);
+  set_stones(&mut world, [1, 1, 1, 0, 0, 0]);
+  assert!(matches!(
+    world.start_incantation(leader),
+    Err(Error::NotEnoughPlayers(2, 1))
+  ));
+
+  player(&mut world, 2);
+  set_stones(&mut world, [1, 1, 0, 0, 0, 0]);
+  assert!(matches!(
+    world.start_incantation(leader),
+    Err(Error::NotEnoughStones(Ore::Sibur, 1, 0))
+  ));
+  assert!(world.incantation(leader).is_none());
+
+  set_stones(&mut world, [1, 1, 1, 0, 0, 0]);
+  world.start_incantation(leader).unwrap();
+  assert!(matches!(
+    world.start_incantation(leader),
+    Err(Error::AlreadyIncanting(id)) if id == leader
+  ));
+}
+
+#[test]
+fn finish_checks_players_and_stones_again() {
+  let mut world = world();
+  let leader = player(&mut world, 1);
+  set_stones(&mut world, [1, 0, 0, 0, 0, 0]);
+  world.start_incantation(leader).unwrap();
+  world.take(leader, Item::Ore(Ore::Linemate)).unwrap();
+  assert!(matches!(
+    world.finish_incantation(leader),
+    Err(Error::NotEnoughStones(Ore::Linemate, 1, 0))
+  ));
+  assert_eq!(level(&world, leader), 1);
+  assert!(matches!(
+    world.finish_incantation(leader),
+    Err(Error::NoIncantation(id)) if id == leader
+  ));
+
+  let (leader, other) = (player(&mut world, 2), player(&mut world, 2));
+  set_stones(&mut world, [1, 1, 1, 0, 0, 0]);
+  world.start_incantation(leader).unwrap();
+  world.remove_player(other).unwrap();
+  assert!(matches!(
+    world.finish_incantation(leader),
+    Err(Error::NotEnoughPlayers(2, 1))
+  ));
+  assert_eq!(stones(&world), [1, 1, 1, 0, 0, 0]);
+  assert_eq!(level(&world, leader), 2);
+}
+
+#[test]
+fn latecomers_dont_take_part() {
+  let mut world = world();
+  let leader = player(&mut world, 1);
+  set_stones(&mut world, [1, 0, 0, 0, 0, 0]);
+  world.start_incantation(leader).unwrap();
+  let latecomer = player(&mut world, 1);
+  assert_eq!(world.finish_incantation(leader).unwrap(), [leader]);
+  assert_eq!(level(&world, latecomer), 1);
+}
+
+#[test]
+fn max_level_cant_elevate() {
+  let mut world = world();
+  let leader = player(&mut world, MAX_LEVEL);
+  set_stones(&mut world, [9; ORE_KINDS]);
+  assert!(matches!(
+    world.start_incantation(leader),
+    Err(Error::MaxLevelReached(id)) if id == leader
+  ));
+}
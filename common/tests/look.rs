@@ -0,0 +1,117 @@
+use common::zappy::{Item, Orientation, Position, World, WorldConfig};
+
+/// A 5x4 map, so that mixing up the axes shows.
+fn world() -> World {
+  World::new(WorldConfig {
+    width: 5,
+    height: 4,
+    teams: vec!["red".into()],
+    ..Default::default()
+  })
+  .unwrap()
+}
+
+fn face(world: &mut World, id: usize, orientation: Orientation) {
+  while world.player(id).unwrap().orientation() != orientation {
+    world.turn_right(id).unwrap();
+  }
+}
+
+/// Walks a player to `position`, then turns it towards `orientation`.
+fn place(world: &mut World, id: usize, position: Position, orientation: Orientation) {
+  face(world, id, Orientation::East);
+  while world.player(id).unwrap().position().x != position.x {
+    world.forward(id).unwrap();
+  }
+  face(world, id, Orientation::South);
+  while world.player(id).unwrap().position().y != position.y {
+    world.forward(id).unwrap();
+  }
+  face(world, id, orientation);
+}
+
+fn seen(world: &World, id: usize) -> Vec<(usize, usize)> {
+  world
+    .look(id)
+    .unwrap()
+    .0
+    .iter()
+    .map(|tile| (tile.position.x, tile.position.y))
+    .collect()
+}
+
+#[test]
+fn cone_for_each_orientation() {
+  use Orientation::*;
+  let cases = [
+    (North, (2, 2), vec![(2, 2), (1, 1), (2, 1), (3, 1)]),
+    (East, (2, 2), vec![(2, 2), (3, 1), (3, 2), (3, 3)]),
+    (South, (2, 2), vec![(2, 2), (3, 3), (2, 3), (1, 3)]),
+    (West, (2, 2), vec![(2, 2), (1, 3), (1, 2), (1, 1)]),
+    (North, (0, 0), vec![(0, 0), (4, 3), (0, 3), (1, 3)]),
+    (East, (4, 0), vec![(4, 0), (0, 3), (0, 0), (0, 1)]),
+    (South, (0, 3), vec![(0, 3), (1, 0), (0, 0), (4, 0)]),
+    (West, (0, 3), vec![(0, 3), (4, 0), (4, 3), (4, 2)]),
+  ];
+  let mut world = world();
+  let id = world.add_player("red").unwrap().0;
+  for (orientation, (x, y), expected) in cases {
+    place(&mut world, id, Position::new(x, y), orientation);
+    assert_eq!(seen(&world, id), expected, "{orientation:?} at ({x}, {y})");
+  }
+}
+
+#[test]
+fn cone_grows_with_the_level() {
+  let mut world = world();
+  let id = world.add_player("red").unwrap().0;
+  world.player_mut(id).unwrap().level_up().unwrap();
+  place(&mut world, id, Position::new(0, 0), Orientation::North);
+  assert_eq!(
+    seen(&world, id),
+    [
+      (0, 0),
+      (4, 3),
+      (0, 3),
+      (1, 3),
+      (3, 2),
+      (4, 2),
+      (0, 2),
+      (1, 2),
+      (2, 2),
+    ]
+  );
+
+  world.player_mut(id).unwrap().level_up().unwrap();
+  place(&mut world, id, Position::new(4, 1), Orientation::West);
+  let seen = seen(&world, id);
+  assert_eq!(seen.len(), 16);
+  assert_eq!(
+    seen[9..],
+    [(1, 0), (1, 3), (1, 2), (1, 1), (1, 0), (1, 3), (1, 2)]
+  );
+}
+
+#[test]
+fn tiles_show_players_and_items() {
+  let mut world = world();
+  let (id, other) = (
+    world.add_player("red").unwrap().0,
+    world.add_player("red").unwrap().0,
+  );
+  place(&mut world, id, Position::new(1, 1), Orientation::South);
+  place(&mut world, other, Position::new(0, 2), Orientation::North);
+  for i in 0..world.x() * world.y() {
+    let tile = world.tile_at_index_mut(i);
+    for item in Item::iter() {
+      tile.take(item, tile.count(item));
+    }
+  }
+  world.tile_at_pos_mut(0, 2).add(Item::Nourriture, 2);
+
+  let look = world.look(id).unwrap();
+  let players = look.0.iter().map(|tile| tile.players).collect::<Vec<_>>();
+  assert_eq!(players, [1, 0, 0, 1]);
+  assert_eq!(look.0[3].items.count(Item::Nourriture), 2);
+  assert!(look.0[..3].iter().all(|tile| tile.items.is_empty()));
+}
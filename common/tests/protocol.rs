@@ -0,0 +1,194 @@
+use {
+  common::{
+    protocol::{AiCommand, AiResponse, GuiCommand, GuiEvent, LookTile},
+    zappy::{Inventory, Item, Orientation, Position, constants::ITEM_KINDS},
+  },
+  proptest::{collection::vec, prelude::*},
+  std::{fmt::Display, str::FromStr},
+};
+
+fn round_trip<T: Display + FromStr + PartialEq + std::fmt::Debug>(message: T)
+where
+  T::Err: std::fmt::Debug,
+{
+  let line = message.to_string();
+  assert_eq!(line.parse::<T>().unwrap(), message, "line '{line}'");
+}
+
+fn item() -> impl Strategy<Value = Item> {
+  (0..ITEM_KINDS).prop_map(|i| Item::try_from(i).unwrap())
+}
+
+fn inventory() -> impl Strategy<Value = Inventory> {
+  prop::array::uniform7(0..20usize).prop_map(Inventory::from)
+}
+
+fn position() -> impl Strategy<Value = Position> {
+  (0..200usize, 0..200usize).prop_map(|(x, y)| Position::new(x, y))
+}
+
+fn orientation() -> impl Strategy<Value = Orientation> {
+  prop_oneof![
+    Just(Orientation::North),
+    Just(Orientation::East),
+    Just(Orientation::South),
+    Just(Orientation::West),
+  ]
+}
+
+fn text() -> impl Strategy<Value = String> {
+  "[a-zA-Z0-9_][a-zA-Z0-9_ ,.!?]{0,30}"
+}
+
+fn ai_command() -> impl Strategy<Value = AiCommand> {
+  use AiCommand::*;
+  prop_oneof![
+    Just(Forward),
+    Just(Right),
+    Just(Left),
+    Just(Look),
+    Just(Inventory),
+    text().prop_map(Broadcast),
+    Just(ConnectNbr),
+    Just(Fork),
+    Just(Eject),
+    item().prop_map(Take),
+    item().prop_map(Set),
+    Just(Incantation),
+  ]
+}
+
+fn look_tile() -> impl Strategy<Value = LookTile> {
+  (0..4usize, prop::array::uniform7(0..3usize)).prop_map(|(players, items)| LookTile {
+    players,
+    items: Inventory::from(items),
+  })
+}
+
+fn ai_response() -> impl Strategy<Value = AiResponse> {
+  use AiResponse::*;
+  prop_oneof![
+    Just(Welcome),
+    Just(Ok),
+    Just(Ko),
+    Just(Dead),
+    any::<usize>().prop_map(Slots),
+    (any::<usize>(), any::<usize>()).prop_map(|(x, y)| MapSize(x, y)),
+    vec(look_tile(), 1..10).prop_map(Look),
+    inventory().prop_map(Inventory),
+    (0..9u8, text()).prop_map(|(k, text)| Message(k, text)),
+    (0..9u8).prop_map(Eject),
+    Just(ElevationUnderway),
+    (1..=8u8).prop_map(CurrentLevel),
+  ]
+}
+
+fn gui_command() -> impl Strategy<Value = GuiCommand> {
+  use GuiCommand::*;
+  prop_oneof![
+    Just(Msz),
+    position().prop_map(Bct),
+    Just(Mct),
+    Just(Tna),
+    any::<usize>().prop_map(Ppo),
+    any::<usize>().prop_map(Plv),
+    any::<usize>().prop_map(Pin),
+    Just(Sgt),
+    any::<u64>().prop_map(Sst),
+  ]
+}
+
+fn gui_event() -> impl Strategy<Value = GuiEvent> {
+  use GuiEvent::*;
+  let id = || 0..10_000usize;
+  prop_oneof![
+    (id(), id()).prop_map(|(x, y)| Msz(x, y)),
+    (position(), inventory()).prop_map(|(p, items)| Bct(p, items)),
+    text().prop_map(Tna),
+    (id(), position(), orientation(), 1..=8u8, text()).prop_map(
+      |(id, position, orientation, level, team)| Pnw {
+        id,
+        position,
+        orientation,
+        level,
+        team,
+      }
+    ),
+    (id(), position(), orientation()).prop_map(|(id, p, o)| Ppo(id, p, o)),
+    (id(), 1..=8u8).prop_map(|(id, level)| Plv(id, level)),
+    (id(), position(), inventory()).prop_map(|(id, p, items)| Pin(id, p, items)),
+    id().prop_map(Pex),
+    (id(), text()).prop_map(|(id, message)| Pbc(id, message)),
+    (position(), 1..=8u8, vec(id(), 1..6)).prop_map(|(p, level, ids)| Pic(p, level, ids)),
+    (position(), any::<bool>()).prop_map(|(p, success)| Pie(p, success)),
+    id().prop_map(Pfk),
+    (id(), item()).prop_map(|(id, item)| Pdr(id, item)),
+    (id(), item()).prop_map(|(id, item)| Pgt(id, item)),
+    id().prop_map(Pdi),
+    (id(), proptest::option::of(id()), position()).prop_map(|(e, p, pos)| Enw(e, p, pos)),
+    id().prop_map(Ebo),
+    id().prop_map(Edi),
+    any::<u64>().prop_map(Sgt),
+    any::<u64>().prop_map(Sst),
+    text().prop_map(Seg),
+    text().prop_map(Smg),
+    Just(Suc),
+    Just(Sbp),
+  ]
+}
+
+proptest! {
+  #[test]
+  fn ai_commands_round_trip(command in ai_command()) {
+    round_trip(command);
+  }
+
+  #[test]
+  fn ai_responses_round_trip(response in ai_response()) {
+    round_trip(response);
+  }
+
+  #[test]
+  fn gui_commands_round_trip(command in gui_command()) {
+    round_trip(command);
+  }
+
+  #[test]
+  fn gui_events_round_trip(event in gui_event()) {
+    round_trip(event);
+  }
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+  for line in [
+    "",
+    "forward",
+    "Forward now",
+    "Take",
+    "Take gold",
+    "Set food food",
+    "Broadcast",
+    "Connect_nbr 1",
+  ] {
+    assert!(line.parse::<AiCommand>().is_err(), "'{line}'");
+  }
+  for line in [
+    "bct 1",
+    "bct 1 2 3",
+    "ppo",
+    "ppo #x",
+    "sst",
+    "sst -1",
+    "msz 1",
+    "nop",
+  ] {
+    assert!(line.parse::<GuiCommand>().is_err(), "'{line}'");
+  }
+}
+
+#[test]
+fn player_numbers_may_omit_the_hash() {
+  assert_eq!("ppo 4".parse::<GuiCommand>().unwrap(), GuiCommand::Ppo(4));
+  assert_eq!("ppo #4".parse::<GuiCommand>().unwrap(), GuiCommand::Ppo(4));
+}
@@ -0,0 +1,120 @@
+use {
+  common::zappy::{Error, Item, Ore, SNAPSHOT_VERSION, Snapshot, World, WorldConfig},
+  serde_json::Value,
+};
+
+/// A world in the middle of a game: players, a laid egg and an ongoing incantation.
+fn world() -> World {
+  let mut world = World::new(WorldConfig {
+    seed: 99,
+    width: 6,
+    height: 5,
+    teams: vec!["red".into(), "blue".into()],
+    clients_per_team: 3,
+    ..Default::default()
+  })
+  .unwrap();
+  let leader = world.add_player("red").unwrap().0;
+  let other = world.add_player("blue").unwrap().0;
+  world.lay_egg(other).unwrap();
+  let position = world.player(leader).unwrap().position();
+  world
+    .tile_at_pos_mut(position.x, position.y)
+    .add(Item::Ore(Ore::Linemate), 1);
+  world.start_incantation(leader).unwrap();
+  world.tick(45);
+  world
+}
+
+fn json(world: &World) -> String {
+  world.snapshot().to_json().unwrap()
+}
+
+/// Plays the same turns on both worlds and checks they stay identical.
+fn assert_same_continuation(mut a: World, mut b: World) {
+  assert_eq!(json(&a), json(&b));
+  for world in [&mut a, &mut b] {
+    let id = world.add_player("blue").unwrap().0;
+    world.forward(id).unwrap();
+    world.tick(300);
+  }
+  assert_eq!(json(&a), json(&b));
+}
+
+fn corrupt(edit: impl FnOnce(&mut Value)) -> Error {
+  let mut value = serde_json::from_str::<Value>(&json(&world())).unwrap();
+  edit(&mut value["world"]);
+  let snapshot = Snapshot::from_json(&value.to_string()).unwrap();
+  match World::restore(snapshot) {
+    Ok(_) => panic!("corrupt snapshot restored"),
+    Err(err) => err,
+  }
+}
+
+#[test]
+fn json_round_trip() {
+  let world = world();
+  let snapshot = Snapshot::from_json(&json(&world)).unwrap();
+  assert_eq!(snapshot.version(), SNAPSHOT_VERSION);
+  assert_same_continuation(world, World::restore(snapshot).unwrap());
+}
+
+#[test]
+fn bincode_round_trip() {
+  let world = world();
+  let bytes = world.snapshot().to_bincode().unwrap();
+  let restored = World::restore(Snapshot::from_bincode(&bytes).unwrap()).unwrap();
+  assert_same_continuation(world, restored);
+}
+
+#[test]
+fn other_versions_are_rejected() {
+  let mut value = serde_json::from_str::<Value>(&json(&world())).unwrap();
+  value["version"] = 1.into();
+  assert!(matches!(
+    Snapshot::from_json(&value.to_string()),
+    Err(Error::UnsupportedSnapshotVersion(1))
+  ));
+
+  let mut bytes = world().snapshot().to_bincode().unwrap();
+  assert_eq!(bytes[0], SNAPSHOT_VERSION as u8);
+  bytes[0] = 1;
+  assert!(matches!(
+    Snapshot::from_bincode(&bytes),
+    Err(Error::UnsupportedSnapshotVersion(1))
+  ));
+}
+
+#[test]
+fn inconsistent_worlds_are_rejected() {
+  let cases: [fn(&mut Value); 8] = [
+    |w| w["tiles"].as_array_mut().unwrap().truncate(3),
+    |w| w["x"] = usize::MAX.into(),
+    |w| w["players"]["0"]["position"]["x"] = 6.into(),
+    |w| w["players"]["0"]["team"] = "green".into(),
+    |w| {
+      let egg = w["eggs"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .next()
+        .unwrap()
+        .clone();
+      w["eggs"][egg]["position"]["y"] = 5.into();
+    },
+    |w| w["teams"]["red"]["players"] = vec![0, 42].into(),
+    |w| w["teams"]["blue"]["eggs"] = vec![1000].into(),
+    |w| w["incantations"]["0"]["participants"] = vec![0, 7].into(),
+  ];
+  for edit in cases {
+    assert!(matches!(corrupt(edit), Error::InvalidSnapshot(_)));
+  }
+}
+
+#[test]
+fn zero_frequency_is_rejected() {
+  assert!(matches!(
+    corrupt(|w| w["frequency"] = 0.into()),
+    Error::InvalidFrequency(0)
+  ));
+}
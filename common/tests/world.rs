@@ -0,0 +1,124 @@
+use common::zappy::{
+  Event, Inventory, Item, Position, World, WorldConfig,
+  constants::{FOOD_TIME_UNITS, INITIAL_FOOD, RESPAWN_INTERVAL},
+};
+
+fn config(seed: u64) -> WorldConfig {
+  WorldConfig {
+    seed,
+    width: 12,
+    height: 9,
+    teams: vec!["red".into(), "blue".into()],
+    ..Default::default()
+  }
+}
+
+fn tiles(world: &World) -> Vec<Inventory> {
+  (0..world.x() * world.y())
+    .map(|i| *world.tile_at_index(i).items())
+    .collect()
+}
+
+fn eggs(world: &World) -> Vec<(usize, String, Position)> {
+  world
+    .eggs()
+    .map(|egg| (egg.id(), egg.team().to_string(), egg.position()))
+    .collect()
+}
+
+/// Empties the map, then lets enough time pass for a few respawns.
+fn respawn(world: &mut World) {
+  for i in 0..world.x() * world.y() {
+    let tile = world.tile_at_index_mut(i);
+    for item in Item::iter() {
+      tile.take(item, tile.count(item));
+    }
+  }
+  world.tick(RESPAWN_INTERVAL * 3);
+}
+
+#[test]
+fn same_config_gives_same_world() {
+  let (mut a, mut b) = (
+    World::new(config(42)).unwrap(),
+    World::new(config(42)).unwrap(),
+  );
+  assert_eq!(tiles(&a), tiles(&b));
+  assert_eq!(eggs(&a), eggs(&b));
+
+  respawn(&mut a);
+  respawn(&mut b);
+  assert!(tiles(&a).iter().any(|tile| !tile.is_empty()));
+  assert_eq!(tiles(&a), tiles(&b));
+}
+
+#[test]
+fn same_config_hatches_same_players() {
+  let (mut a, mut b) = (
+    World::new(config(7)).unwrap(),
+    World::new(config(7)).unwrap(),
+  );
+  for team in ["red", "blue", "red"] {
+    let (id_a, id_b) = (a.add_player(team).unwrap().0, b.add_player(team).unwrap().0);
+    let (pa, pb) = (a.player(id_a).unwrap(), b.player(id_b).unwrap());
+    assert_eq!(
+      (pa.position(), pa.orientation()),
+      (pb.position(), pb.orientation())
+    );
+  }
+  assert_eq!(eggs(&a), eggs(&b));
+}
+
+#[test]
+fn other_seed_gives_other_world() {
+  let (a, b) = (
+    World::new(config(1)).unwrap(),
+    World::new(config(2)).unwrap(),
+  );
+  assert_ne!(tiles(&a), tiles(&b));
+}
+
+fn died(events: &[Event]) -> Vec<usize> {
+  events
+    .iter()
+    .filter_map(|event| match event {
+      Event::PlayerDied { id, .. } => Some(*id),
+      _ => None,
+    })
+    .collect()
+}
+
+#[test]
+fn initial_food_lasts_1260_time_units() {
+  assert_eq!(INITIAL_FOOD as u64 * FOOD_TIME_UNITS, 1260);
+  let mut world = World::new(config(3)).unwrap();
+  let id = world.add_player("red").unwrap().0;
+  let position = world.player(id).unwrap().position();
+
+  for _ in 0..1260 {
+    assert!(died(&world.tick(1)).is_empty());
+  }
+  let player = world.player(id).unwrap();
+  assert_eq!(player.inventory().count(Item::Nourriture), 0);
+  assert_eq!(player.life(), 0);
+
+  let events = world.tick(1);
+  assert!(events.contains(&Event::PlayerDied {
+    id,
+    team: "red".into(),
+    position,
+  }));
+  assert!(world.player(id).is_err());
+  assert_eq!(world.players().count(), 0);
+}
+
+#[test]
+fn starving_in_one_tick() {
+  let mut world = World::new(config(3)).unwrap();
+  let id = world.add_player("red").unwrap().0;
+  assert!(died(&world.tick(1260)).is_empty());
+  assert_eq!(died(&world.tick(1)), [id]);
+
+  let id = world.add_player("blue").unwrap().0;
+  assert_eq!(died(&world.tick(1261)), [id]);
+}
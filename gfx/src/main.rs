@@ -8,14 +8,33 @@ use {
     input::{KeyboardKey, get_mouse_wheel_move_y, is_key_down, is_mouse_wheel_moving_y},
     window::Window,
   },
-  common::zappy::{World, constants::TEAM_SIZE},
-  rand::rng,
-  std::error::Error,
+  common::zappy::{World, WorldConfig},
+  rand::random,
+  std::env,
+  thiserror::Error,
 };
 
 const CAMERA_SPEED: f32 = 1.;
 const CAMERA_FOVY_MIN: f32 = 10.;
 const CAMERA_FOVY_MAX: f32 = 280.;
+/// Environment variable holding the seed of a map to reproduce.
+const SEED_VAR: &str = "ZAPPY_SEED";
+
+#[derive(Error, Debug)]
+enum GfxError {
+  #[error("{SEED_VAR} must be an unsigned 64-bit integer, not '{0}'")]
+  InvalidSeed(String),
+
+  #[error(transparent)]
+  World(#[from] common::zappy::Error),
+}
+
+fn seed() -> Result<u64, GfxError> {
+  match env::var(SEED_VAR) {
+    Ok(seed) => seed.parse().map_err(|_| GfxError::InvalidSeed(seed)),
+    Err(_) => Ok(random()),
+  }
+}
 
 fn update_camera(camera: &mut Camera3D) {
   if is_key_down(KeyboardKey::W) {
@@ -52,18 +71,21 @@ impl Scene {
   }
 }
 
-fn gfx() -> Result<(), impl Error> {
+fn gfx() -> Result<(), GfxError> {
   const GRID_SIZE: u32 = 128;
+  let seed = seed()?;
   let window = Window::try_init(1920, 1080, "zappy").expect("Invalid arguments to init_window");
   let current_monitor = window.get_current_monitor();
-  let mut rng = rng();
-  let mut world = World::generate(&mut rng, 128, 128);
-
-  world.add_team("Team 1")?;
-  world.add_team("Team 2")?;
-  world.add_team("Team 3")?;
+  let mut world = World::new(WorldConfig {
+    seed,
+    width: 128,
+    height: 128,
+    teams: vec!["Team 1".into(), "Team 2".into(), "Team 3".into()],
+    ..Default::default()
+  })?;
+  println!("world seed: {} (set {SEED_VAR} to replay it)", world.seed());
 
-  for _ in 0..TEAM_SIZE {
+  for _ in 0..world.team_capacity() {
     world.add_player("Team 1")?;
     world.add_player("Team 2")?;
     world.add_player("Team 3")?;
@@ -99,7 +121,7 @@ fn gfx() -> Result<(), impl Error> {
   );
 
   window.set_target_fps(current_monitor.get_refresh_rate());
-  Ok::<(), common::zappy::Error>(while !window.should_close() {
+  Ok::<(), GfxError>(while !window.should_close() {
     update_camera(&mut camera);
     window.begin_drawing(|pen| {
       use Color::*;
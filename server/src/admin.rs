@@ -0,0 +1,129 @@
+use {
+  crate::clock::ClockMode,
+  common::protocol::Error,
+  std::{fmt, str::FromStr},
+};
+
+/// A command sent by an `ADMIN` client to drive the game clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdminCommand {
+  Pause,
+  Resume,
+  /// Runs this many time units, then pauses again.
+  Step(u64),
+  Unlimited,
+  Status,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdminResponse {
+  Ok,
+  Ko,
+  Status {
+    mode: ClockMode,
+    time: u64,
+    frequency: u64,
+    seed: u64,
+  },
+}
+
+impl FromStr for AdminCommand {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self, Error> {
+    let (name, argument) = match s.split_once(' ') {
+      Some((name, argument)) => (name, Some(argument)),
+      None => (s, None),
+    };
+    Ok(match (name, argument) {
+      ("pause", None) => Self::Pause,
+      ("resume", None) => Self::Resume,
+      ("unlimited", None) => Self::Unlimited,
+      ("status", None) => Self::Status,
+      ("step", Some(steps)) => match steps.parse() {
+        Ok(steps) if steps > 0 => Self::Step(steps),
+        _ => return Err(Error::InvalidArguments(s.to_string())),
+      },
+      ("pause" | "resume" | "unlimited" | "status" | "step", _) => {
+        return Err(Error::InvalidArguments(s.to_string()));
+      }
+      _ => return Err(Error::UnknownMessage(s.to_string())),
+    })
+  }
+}
+
+impl fmt::Display for AdminResponse {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Ok => f.write_str("ok"),
+      Self::Ko => f.write_str("ko"),
+      Self::Status {
+        mode,
+        time,
+        frequency,
+        seed,
+      } => write!(f, "{mode} time {time} frequency {frequency} seed {seed}"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn commands() {
+    for (line, command) in [
+      ("pause", AdminCommand::Pause),
+      ("resume", AdminCommand::Resume),
+      ("unlimited", AdminCommand::Unlimited),
+      ("status", AdminCommand::Status),
+      ("step 1", AdminCommand::Step(1)),
+      ("step 18446744073709551615", AdminCommand::Step(u64::MAX)),
+    ] {
+      assert_eq!(line.parse::<AdminCommand>().unwrap(), command);
+    }
+  }
+
+  #[test]
+  fn invalid_arguments() {
+    for line in [
+      "step 0",
+      "step x",
+      "step -1",
+      "step",
+      "step ",
+      "step 1 2",
+      "pause 1",
+      "status now",
+    ] {
+      assert!(
+        matches!(
+          line.parse::<AdminCommand>(),
+          Err(Error::InvalidArguments(_))
+        ),
+        "{line}"
+      );
+    }
+  }
+
+  #[test]
+  fn unknown_commands() {
+    for line in ["", "Pause", "stop", "step1"] {
+      assert!(
+        matches!(line.parse::<AdminCommand>(), Err(Error::UnknownMessage(_))),
+        "{line}"
+      );
+    }
+  }
+
+  #[test]
+  fn status() {
+    let status = AdminResponse::Status {
+      mode: ClockMode::Step(5),
+      time: 120,
+      frequency: 100,
+      seed: 42,
+    };
+    assert_eq!(status.to_string(), "step 5 time 120 frequency 100 seed 42");
+  }
+}
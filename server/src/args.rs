@@ -0,0 +1,293 @@
+use {
+  crate::connection::{ADMIN_ROLE, GRAPHIC_ROLE},
+  common::zappy::WorldConfig,
+  std::{collections::HashSet, str::FromStr},
+  thiserror::Error,
+};
+
+pub const USAGE: &str = "USAGE: ./server -p port -x width -y height -n name1 name2 ... -c clientsNb \
+[-f freq] [-s seed] [-a]\n  -a  accept ADMIN clients from this machine to pause, step or fast-forward the game";
+
+const DEFAULT_FREQUENCY: u64 = 100;
+/// Largest width or height a map may have.
+const MAX_MAP_SIDE: usize = 1024;
+/// Largest number of eggs a team may start with.
+const MAX_CLIENTS_PER_TEAM: usize = 1024;
+
+#[derive(Error, Debug)]
+pub enum ArgsError {
+  #[error("usage requested")]
+  Help,
+
+  #[error("unknown option '{0}'")]
+  UnknownOption(String),
+
+  #[error("missing value for option {0}")]
+  MissingValue(String),
+
+  #[error("invalid value '{1}' for option {0}")]
+  InvalidValue(String, String),
+
+  #[error("option {0} is given twice")]
+  DuplicateOption(String),
+
+  #[error("option {0} is required")]
+  MissingOption(&'static str),
+
+  #[error("team '{0}' is given twice")]
+  DuplicateTeam(String),
+
+  #[error("team name '{0}' is reserved")]
+  ReservedTeamName(String),
+}
+
+#[derive(Debug)]
+pub struct Args {
+  pub port: u16,
+  pub width: usize,
+  pub height: usize,
+  pub teams: Vec<String>,
+  pub clients_per_team: usize,
+  pub frequency: u64,
+  pub seed: Option<u64>,
+  pub admin: bool,
+}
+
+impl Args {
+  pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, ArgsError> {
+    use ArgsError::*;
+    let mut args = args.into_iter().peekable();
+    let (mut port, mut width, mut height, mut clients_per_team) = (None, None, None, None);
+    let (mut frequency, mut seed, mut admin) = (None, None, false);
+    let mut teams = Vec::new();
+    let mut given = HashSet::new();
+
+    while let Some(option) = args.next() {
+      if !given.insert(option.clone()) {
+        return Err(DuplicateOption(option));
+      }
+      let mut value = || args.next().ok_or(MissingValue(option.clone()));
+      match option.as_str() {
+        "-h" | "--help" => return Err(Help),
+        "-p" => port = Some(positive(&option, value()?)?),
+        "-x" => width = Some(bounded(&option, value()?, MAX_MAP_SIDE)?),
+        "-y" => height = Some(bounded(&option, value()?, MAX_MAP_SIDE)?),
+        "-c" => clients_per_team = Some(bounded(&option, value()?, MAX_CLIENTS_PER_TEAM)?),
+        "-f" => frequency = Some(positive(&option, value()?)?),
+        "-s" => seed = Some(parse(&option, value()?)?),
+        "-a" => admin = true,
+        "-n" => {
+          while let Some(name) = args.next_if(|a| !a.starts_with('-')) {
+            teams.push(name);
+          }
+          if teams.is_empty() {
+            return Err(MissingValue(option));
+          }
+        }
+        _ => return Err(UnknownOption(option)),
+      }
+    }
+
+    let port = port.ok_or(MissingOption("-p"))?;
+    let width = width.ok_or(MissingOption("-x"))?;
+    let height = height.ok_or(MissingOption("-y"))?;
+    if width.checked_mul(height).is_none() {
+      return Err(InvalidValue("-y".to_string(), height.to_string()));
+    }
+    if teams.is_empty() {
+      return Err(MissingOption("-n"));
+    }
+    let clients_per_team = clients_per_team.ok_or(MissingOption("-c"))?;
+
+    let mut names = HashSet::new();
+    for name in &teams {
+      if name == GRAPHIC_ROLE || name == ADMIN_ROLE {
+        return Err(ReservedTeamName(name.clone()));
+      }
+      if !names.insert(name) {
+        return Err(DuplicateTeam(name.clone()));
+      }
+    }
+
+    Ok(Self {
+      port,
+      width,
+      height,
+      teams,
+      clients_per_team,
+      frequency: frequency.unwrap_or(DEFAULT_FREQUENCY),
+      seed,
+      admin,
+    })
+  }
+
+  pub fn world_config(&self, seed: u64) -> WorldConfig {
+    WorldConfig {
+      seed,
+      width: self.width,
+      height: self.height,
+      teams: self.teams.clone(),
+      clients_per_team: self.clients_per_team,
+      frequency: self.frequency,
+      ..Default::default()
+    }
+  }
+}
+
+fn parse<T: FromStr>(option: &str, value: String) -> Result<T, ArgsError> {
+  value
+    .parse()
+    .map_err(|_| ArgsError::InvalidValue(option.to_string(), value))
+}
+
+fn positive<T: FromStr + Default + PartialEq>(option: &str, value: String) -> Result<T, ArgsError> {
+  match parse(option, value.clone())? {
+    v if v == T::default() => Err(ArgsError::InvalidValue(option.to_string(), value)),
+    v => Ok(v),
+  }
+}
+
+fn bounded(option: &str, value: String, max: usize) -> Result<usize, ArgsError> {
+  match positive(option, value.clone())? {
+    v if v > max => Err(ArgsError::InvalidValue(option.to_string(), value)),
+    v => Ok(v),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(args: &str) -> Result<Args, ArgsError> {
+    Args::parse(args.split_whitespace().map(String::from))
+  }
+
+  fn invalid(args: &str) -> (String, String) {
+    match parse(args) {
+      Err(ArgsError::InvalidValue(option, value)) => (option, value),
+      other => panic!("'{args}' gave {other:?}"),
+    }
+  }
+
+  #[test]
+  fn full_command_line() {
+    let args = parse("-p 4242 -x 10 -y 8 -n red blue -c 3 -f 50 -s 7 -a").unwrap();
+    assert_eq!(
+      (args.port, args.width, args.height, args.clients_per_team),
+      (4242, 10, 8, 3)
+    );
+    assert_eq!(args.teams, ["red", "blue"]);
+    assert_eq!((args.frequency, args.seed, args.admin), (50, Some(7), true));
+
+    let args = parse("-n red -c 1 -y 1 -x 1 -p 1").unwrap();
+    assert_eq!(
+      (args.frequency, args.seed, args.admin),
+      (DEFAULT_FREQUENCY, None, false)
+    );
+  }
+
+  #[test]
+  fn missing_options() {
+    for (args, missing) in [
+      ("-x 10 -y 10 -n red -c 3", "-p"),
+      ("-p 4242 -y 10 -n red -c 3", "-x"),
+      ("-p 4242 -x 10 -n red -c 3", "-y"),
+      ("-p 4242 -x 10 -y 10 -c 3", "-n"),
+      ("-p 4242 -x 10 -y 10 -n red", "-c"),
+    ] {
+      assert!(
+        matches!(parse(args), Err(ArgsError::MissingOption(option)) if option == missing),
+        "{args}"
+      );
+    }
+    assert!(matches!(parse("-p 4242 -x"), Err(ArgsError::MissingValue(o)) if o == "-x"));
+    assert!(matches!(parse("-p 4242 -z 1"), Err(ArgsError::UnknownOption(o)) if o == "-z"));
+  }
+
+  #[test]
+  fn duplicate_options() {
+    for args in [
+      "-p 4242 -p 4243 -x 10 -y 10 -n red -c 3",
+      "-p 4242 -x 10 -y 10 -n red -n blue -c 3",
+      "-p 4242 -x 10 -y 10 -n red -c 3 -a -a",
+    ] {
+      assert!(
+        matches!(parse(args), Err(ArgsError::DuplicateOption(_))),
+        "{args}"
+      );
+    }
+    assert!(matches!(
+      parse("-p 4242 -x 10 -y 10 -n red blue red -c 3"),
+      Err(ArgsError::DuplicateTeam(team)) if team == "red"
+    ));
+  }
+
+  #[test]
+  fn team_names() {
+    for reserved in [GRAPHIC_ROLE, ADMIN_ROLE] {
+      let args = format!("-p 4242 -x 10 -y 10 -n red {reserved} -c 3");
+      assert!(matches!(parse(&args), Err(ArgsError::ReservedTeamName(team)) if team == reserved));
+    }
+    assert!(matches!(
+      parse("-p 4242 -x 10 -y 10 -n -c 3"),
+      Err(ArgsError::MissingValue(option)) if option == "-n"
+    ));
+    let args = parse("-p 4242 -x 10 -y 10 -n red blue -c 3").unwrap();
+    assert_eq!(args.teams, ["red", "blue"]);
+  }
+
+  #[test]
+  fn zero_values() {
+    assert_eq!(
+      invalid("-p 0 -x 10 -y 10 -n red -c 3"),
+      ("-p".into(), "0".into())
+    );
+    assert_eq!(
+      invalid("-p 4242 -x 0 -y 10 -n red -c 3"),
+      ("-x".into(), "0".into())
+    );
+    assert_eq!(
+      invalid("-p 4242 -x 10 -y 0 -n red -c 3"),
+      ("-y".into(), "0".into())
+    );
+    assert_eq!(
+      invalid("-p 4242 -x 10 -y 10 -n red -c 0"),
+      ("-c".into(), "0".into())
+    );
+    assert_eq!(
+      invalid("-p 4242 -x 10 -y 10 -n red -c 3 -f 0"),
+      ("-f".into(), "0".into())
+    );
+    assert!(parse("-p 4242 -x 10 -y 10 -n red -c 3 -s 0").is_ok());
+  }
+
+  #[test]
+  fn values_out_of_range() {
+    let max = usize::MAX.to_string();
+    assert_eq!(
+      invalid(&format!("-p 4242 -x {max} -y 2 -n red -c 3")).0,
+      "-x"
+    );
+    assert_eq!(
+      invalid(&format!("-p 4242 -x 2 -y {max} -n red -c 3")).0,
+      "-y"
+    );
+    assert_eq!(
+      invalid(&format!("-p 4242 -x 2 -y 2 -n red -c {max}")).0,
+      "-c"
+    );
+    assert_eq!(invalid("-p 65536 -x 2 -y 2 -n red -c 3").0, "-p");
+    assert_eq!(invalid("-p 4242 -x 2 -y 2 -n red -c 3 -f x").0, "-f");
+
+    let side = MAX_MAP_SIDE.to_string();
+    let args = parse(&format!(
+      "-p 4242 -x {side} -y {side} -n red -c {MAX_CLIENTS_PER_TEAM}"
+    ))
+    .unwrap();
+    assert_eq!((args.width, args.height), (MAX_MAP_SIDE, MAX_MAP_SIDE));
+    assert_eq!(
+      invalid(&format!("-p 4242 -x {} -y 2 -n red -c 3", MAX_MAP_SIDE + 1)).0,
+      "-x"
+    );
+  }
+}
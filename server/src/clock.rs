@@ -0,0 +1,165 @@
+use std::{
+  fmt,
+  time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockMode {
+  /// `frequency` time units per second.
+  Running,
+  Paused,
+  /// Runs this many time units as fast as possible, one per turn of the event loop, then pauses.
+  Step(u64),
+  /// One time unit per turn of the event loop, without waiting.
+  Unlimited,
+}
+
+/// Turns wall-clock time into game time units, `frequency` of them per second.
+pub struct Clock {
+  mode: ClockMode,
+  frequency: u64,
+  time_unit: Duration,
+  next_tick: Instant,
+}
+
+impl Clock {
+  pub fn new(frequency: u64) -> Self {
+    let time_unit = time_unit(frequency);
+    Self {
+      mode: ClockMode::Running,
+      frequency,
+      time_unit,
+      next_tick: Instant::now() + time_unit,
+    }
+  }
+
+  pub fn frequency(&self) -> u64 {
+    self.frequency
+  }
+
+  pub fn mode(&self) -> ClockMode {
+    self.mode
+  }
+
+  /// Switches modes, starting a fresh time unit when the clock goes back to running.
+  pub fn set_mode(&mut self, mode: ClockMode) {
+    if mode == ClockMode::Running && self.mode != ClockMode::Running {
+      self.next_tick = Instant::now() + self.time_unit;
+    }
+    self.mode = mode;
+  }
+
+  /// Time left before the next time unit elapses, none while paused.
+  pub fn timeout(&self) -> Option<Duration> {
+    match self.mode {
+      ClockMode::Running => Some(self.next_tick.saturating_duration_since(Instant::now())),
+      ClockMode::Paused => None,
+      ClockMode::Step(_) | ClockMode::Unlimited => Some(Duration::ZERO),
+    }
+  }
+
+  /// Counts the time units elapsed since the last call.
+  pub fn advance(&mut self) -> u64 {
+    match self.mode {
+      ClockMode::Running => {}
+      ClockMode::Paused => return 0,
+      ClockMode::Step(steps) => {
+        self.mode = match steps {
+          0 | 1 => ClockMode::Paused,
+          steps => ClockMode::Step(steps - 1),
+        };
+        return steps.min(1);
+      }
+      ClockMode::Unlimited => return 1,
+    }
+    let now = Instant::now();
+    if now < self.next_tick {
+      return 0;
+    }
+    let late = (now - self.next_tick).as_nanos() / self.time_unit.as_nanos();
+    let elapsed = late as u64 + 1;
+    self.next_tick += self.time_unit.mul_f64(elapsed as f64);
+    elapsed
+  }
+
+  /// Changes the length of a time unit, keeping the share of the current one already elapsed.
+  pub fn set_frequency(&mut self, frequency: u64) {
+    let now = Instant::now();
+    let remaining = self.next_tick.saturating_duration_since(now).as_secs_f64();
+    let share = remaining / self.time_unit.as_secs_f64();
+    self.frequency = frequency;
+    self.time_unit = time_unit(frequency);
+    self.next_tick = now + self.time_unit.mul_f64(share);
+  }
+}
+
+fn time_unit(frequency: u64) -> Duration {
+  Duration::from_secs_f64(1. / frequency as f64).max(Duration::from_nanos(1))
+}
+
+impl fmt::Display for ClockMode {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Running => f.write_str("running"),
+      Self::Paused => f.write_str("paused"),
+      Self::Step(steps) => write!(f, "step {steps}"),
+      Self::Unlimited => f.write_str("unlimited"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::thread};
+
+  #[test]
+  fn step_runs_one_unit_per_turn_then_pauses() {
+    let mut clock = Clock::new(1);
+    clock.set_mode(ClockMode::Step(3));
+    for left in [2, 1] {
+      assert_eq!(clock.timeout(), Some(Duration::ZERO));
+      assert_eq!(clock.advance(), 1);
+      assert_eq!(clock.mode(), ClockMode::Step(left));
+    }
+    assert_eq!(clock.advance(), 1);
+    assert_eq!(clock.mode(), ClockMode::Paused);
+    assert_eq!(clock.timeout(), None);
+    assert_eq!(clock.advance(), 0);
+  }
+
+  #[test]
+  fn huge_steps_still_run_one_unit_per_turn() {
+    let mut clock = Clock::new(100);
+    clock.set_mode(ClockMode::Step(u64::MAX));
+    assert_eq!(clock.advance(), 1);
+    assert_eq!(clock.mode(), ClockMode::Step(u64::MAX - 1));
+  }
+
+  #[test]
+  fn unlimited_never_waits() {
+    let mut clock = Clock::new(1);
+    clock.set_mode(ClockMode::Unlimited);
+    for _ in 0..1000 {
+      assert_eq!(clock.timeout(), Some(Duration::ZERO));
+      assert_eq!(clock.advance(), 1);
+    }
+    assert_eq!(clock.mode(), ClockMode::Unlimited);
+  }
+
+  #[test]
+  fn running_follows_the_wall_clock() {
+    let mut clock = Clock::new(1);
+    assert_eq!(clock.advance(), 0);
+    assert!(clock.timeout().is_some_and(|t| t <= Duration::from_secs(1)));
+
+    clock.set_frequency(1000);
+    thread::sleep(Duration::from_millis(20));
+    assert!(clock.advance() >= 10);
+
+    clock.set_mode(ClockMode::Paused);
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(clock.advance(), 0);
+    clock.set_mode(ClockMode::Running);
+    assert_eq!(clock.advance(), 0);
+  }
+}
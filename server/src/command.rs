@@ -0,0 +1,32 @@
+use common::protocol::AiCommand;
+
+/// Time units an incantation takes between its start and its outcome.
+pub const INCANTATION_TIME: u64 = 300;
+
+/// Time units a command takes before it completes. An incantation is checked as soon as it comes
+/// up and only then takes its `INCANTATION_TIME`.
+pub fn cost(command: &AiCommand) -> u64 {
+  match command {
+    AiCommand::Forward
+    | AiCommand::Right
+    | AiCommand::Left
+    | AiCommand::Look
+    | AiCommand::Broadcast(_)
+    | AiCommand::Eject
+    | AiCommand::Take(_)
+    | AiCommand::Set(_) => 7,
+    AiCommand::Inventory => 1,
+    AiCommand::ConnectNbr | AiCommand::Incantation => 0,
+    AiCommand::Fork => 42,
+  }
+}
+
+/// What a player's queue holds: its own commands, the outcome of an incantation it leads, and
+/// the `ko` owed for a line that isn't a command or that overflowed the queue, answered in turn
+/// like any other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Task {
+  Command(AiCommand),
+  FinishIncantation,
+  Reject,
+}
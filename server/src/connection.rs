@@ -0,0 +1,126 @@
+use {
+  mio::net::TcpStream,
+  std::{
+    fmt,
+    io::{self, ErrorKind, Read, Write},
+    net::SocketAddr,
+  },
+};
+
+const READ_CHUNK_SIZE: usize = 4096;
+const MAX_LINE_LENGTH: usize = 8192;
+
+pub const GRAPHIC_ROLE: &str = "GRAPHIC";
+pub const ADMIN_ROLE: &str = "ADMIN";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+  /// Greeted, still waiting for a team name, `GRAPHIC` or `ADMIN`.
+  Pending,
+  Ai(usize),
+  Graphic,
+  Admin,
+  /// The player starved; its connection only waits for `dead` to be written.
+  Dead,
+}
+
+/// Newline-delimited text connection over a non-blocking socket.
+pub struct Connection {
+  stream: TcpStream,
+  address: SocketAddr,
+  input: Vec<u8>,
+  output: Vec<u8>,
+  closing: bool,
+  pub role: Role,
+}
+
+impl Connection {
+  pub fn new(stream: TcpStream, address: SocketAddr) -> Self {
+    Self {
+      stream,
+      address,
+      input: Vec::new(),
+      output: Vec::new(),
+      closing: false,
+      role: Role::Pending,
+    }
+  }
+
+  pub fn stream_mut(&mut self) -> &mut TcpStream {
+    &mut self.stream
+  }
+
+  pub fn address(&self) -> SocketAddr {
+    self.address
+  }
+
+  /// Reads everything available on the socket. Returns `false` once the peer closed its end.
+  pub fn receive(&mut self) -> io::Result<bool> {
+    let mut chunk = [0; READ_CHUNK_SIZE];
+    loop {
+      match self.stream.read(&mut chunk) {
+        Ok(0) => return Ok(false),
+        Ok(n) => self.input.extend_from_slice(&chunk[..n]),
+        Err(err) if err.kind() == ErrorKind::ConnectionReset => return Ok(false),
+        Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+        Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+        Err(err) => return Err(err),
+      }
+    }
+    let pending = self
+      .input
+      .rsplit(|&b| b == b'\n')
+      .next()
+      .map_or(0, <[u8]>::len);
+    if pending > MAX_LINE_LENGTH {
+      return Err(io::Error::new(ErrorKind::InvalidData, "line too long"));
+    }
+    Ok(true)
+  }
+
+  /// Takes every complete line received so far, without its line terminator.
+  pub fn lines(&mut self) -> Vec<String> {
+    let Some(end) = self.input.iter().rposition(|&b| b == b'\n') else {
+      return Vec::new();
+    };
+    let complete = self.input.drain(..=end).collect::<Vec<_>>();
+    complete[..end]
+      .split(|&b| b == b'\n')
+      .map(|line| String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).into_owned())
+      .collect()
+  }
+
+  pub fn send(&mut self, message: impl fmt::Display) {
+    let _ = writeln!(self.output, "{message}");
+  }
+
+  /// Closes the connection once everything sent so far has been written.
+  pub fn close(&mut self) {
+    self.closing = true;
+  }
+
+  /// Whether the connection is on its way out, in which case its input is ignored.
+  pub fn is_closing(&self) -> bool {
+    self.closing
+  }
+
+  pub fn is_closed(&self) -> bool {
+    self.closing && self.output.is_empty()
+  }
+
+  /// Writes as much buffered output as the socket accepts.
+  pub fn flush(&mut self) -> io::Result<()> {
+    while !self.output.is_empty() {
+      match self.stream.write(&self.output) {
+        Ok(0) => return Err(ErrorKind::WriteZero.into()),
+        Ok(n) => {
+          self.output.drain(..n);
+        }
+        Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+        Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+        Err(err) => return Err(err),
+      }
+    }
+    Ok(())
+  }
+}
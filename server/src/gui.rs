@@ -0,0 +1,86 @@
+use common::{
+  protocol::{self, GuiCommand, GuiEvent},
+  zappy::{Egg, Player, Position, World},
+};
+
+/// Answers a monitor query, `sbp` when it names something that doesn't exist. `sst` is the only
+/// one that changes the world, and the server tells every monitor once it follows the change.
+pub fn query(world: &mut World, command: GuiCommand) -> Vec<GuiEvent> {
+  let player = |id| world.player(id).ok();
+  let replies = match command {
+    GuiCommand::Msz => Some(vec![msz(world)]),
+    GuiCommand::Bct(p) => (p.x < world.x() && p.y < world.y()).then(|| vec![bct(world, p)]),
+    GuiCommand::Mct => Some(mct(world)),
+    GuiCommand::Tna => Some(tna(world)),
+    GuiCommand::Ppo(id) => player(id).map(|p| vec![ppo(p)]),
+    GuiCommand::Plv(id) => player(id).map(|p| vec![plv(p)]),
+    GuiCommand::Pin(id) => player(id).map(|p| vec![pin(p)]),
+    GuiCommand::Sgt => Some(vec![GuiEvent::Sgt(world.frequency())]),
+    GuiCommand::Sst(frequency) => world.set_frequency(frequency).ok().map(|_| vec![]),
+  };
+  replies.unwrap_or_else(|| vec![GuiEvent::Sbp])
+}
+
+/// Everything a monitor needs to draw the game when it connects.
+pub fn dump(world: &World) -> Vec<GuiEvent> {
+  let mut events = vec![msz(world), GuiEvent::Sgt(world.frequency())];
+  events.extend(mct(world));
+  events.extend(tna(world));
+  events.extend(world.players().map(pnw));
+  events.extend(world.eggs().map(enw));
+  events
+}
+
+pub fn msz(world: &World) -> GuiEvent {
+  GuiEvent::Msz(world.x(), world.y())
+}
+
+pub fn bct(world: &World, position: Position) -> GuiEvent {
+  let tile = world.tile_at_pos(position.x, position.y);
+  GuiEvent::Bct(position, *tile.items())
+}
+
+pub fn mct(world: &World) -> Vec<GuiEvent> {
+  (0..world.y())
+    .flat_map(|y| (0..world.x()).map(move |x| Position::new(x, y)))
+    .map(|position| bct(world, position))
+    .collect()
+}
+
+pub fn tna(world: &World) -> Vec<GuiEvent> {
+  world.teams().keys().cloned().map(GuiEvent::Tna).collect()
+}
+
+pub fn pnw(player: &Player) -> GuiEvent {
+  GuiEvent::Pnw {
+    id: player.id(),
+    position: player.position(),
+    orientation: player.orientation(),
+    level: player.level(),
+    team: player.team().to_string(),
+  }
+}
+
+pub fn ppo(player: &Player) -> GuiEvent {
+  GuiEvent::Ppo(player.id(), player.position(), player.orientation())
+}
+
+pub fn plv(player: &Player) -> GuiEvent {
+  GuiEvent::Plv(player.id(), player.level())
+}
+
+pub fn pin(player: &Player) -> GuiEvent {
+  GuiEvent::Pin(player.id(), player.position(), *player.inventory())
+}
+
+pub fn enw(egg: &Egg) -> GuiEvent {
+  GuiEvent::Enw(egg.id(), egg.laid_by(), egg.position())
+}
+
+/// Reply to a line that isn't a monitor query.
+pub fn rejected(err: &protocol::Error) -> GuiEvent {
+  match err {
+    protocol::Error::UnknownMessage(_) => GuiEvent::Suc,
+    protocol::Error::InvalidArguments(_) => GuiEvent::Sbp,
+  }
+}
@@ -1,3 +1,63 @@
-fn main() {
-  println!("Hello, world!");
+mod admin;
+mod args;
+mod clock;
+mod command;
+mod connection;
+mod gui;
+mod scheduler;
+mod server;
+
+use {
+  crate::{
+    args::{Args, ArgsError, USAGE},
+    server::Server,
+  },
+  common::zappy::World,
+  std::{env, process::ExitCode},
+};
+
+fn main() -> ExitCode {
+  let args = match Args::parse(env::args().skip(1)) {
+    Ok(args) => args,
+    Err(ArgsError::Help) => {
+      println!("{USAGE}");
+      return ExitCode::SUCCESS;
+    }
+    Err(err) => {
+      eprintln!("error: {err}\n{USAGE}");
+      return ExitCode::FAILURE;
+    }
+  };
+
+  let seed = args.seed.unwrap_or_else(rand::random);
+  let world = match World::new(args.world_config(seed)) {
+    Ok(world) => world,
+    Err(err) => {
+      eprintln!("error: failed to create world: {err}");
+      return ExitCode::FAILURE;
+    }
+  };
+
+  let mut server = match Server::bind(world, args.port, args.admin) {
+    Ok(server) => server,
+    Err(err) => {
+      eprintln!("error: failed to listen on port {}: {err}", args.port);
+      return ExitCode::FAILURE;
+    }
+  };
+  println!(
+    "listening on port {} with seed {seed}",
+    server.local_port().unwrap_or(args.port)
+  );
+
+  match server.run() {
+    Ok(winner) => {
+      println!("team '{winner}' won the game");
+      ExitCode::SUCCESS
+    }
+    Err(err) => {
+      eprintln!("error: {err}");
+      ExitCode::FAILURE
+    }
+  }
 }
@@ -0,0 +1,235 @@
+use std::collections::{BTreeMap, VecDeque};
+
+/// How many commands a player may have waiting, the one being executed included. Commands beyond
+/// it are answered `ko` in turn, once the replies already owed are sent.
+pub const MAX_PENDING_COMMANDS: usize = 10;
+
+struct Queue<T> {
+  /// Time unit at which the command in front of `pending` completes.
+  deadline: u64,
+  pending: VecDeque<(u64, T)>,
+}
+
+/// Per-player command queues, each running one command at a time on the game clock.
+pub struct Scheduler<T> {
+  queues: BTreeMap<usize, Queue<T>>,
+}
+
+impl<T> Default for Scheduler<T> {
+  fn default() -> Self {
+    Self {
+      queues: BTreeMap::new(),
+    }
+  }
+}
+
+impl<T> Scheduler<T> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Queues a command costing `cost` time units, returning whether it starts right away, or
+  /// handing it back if the player's queue is full.
+  pub fn push(&mut self, player: usize, now: u64, cost: u64, command: T) -> Result<bool, T> {
+    if self
+      .queues
+      .get(&player)
+      .is_some_and(|queue| queue.pending.len() >= MAX_PENDING_COMMANDS)
+    {
+      return Err(command);
+    }
+    Ok(self.force_push(player, now, cost, command))
+  }
+
+  /// Queues a command behind everything the player has queued, whatever the queue's capacity,
+  /// returning whether it starts right away.
+  pub fn force_push(&mut self, player: usize, now: u64, cost: u64, command: T) -> bool {
+    let queue = self.queues.entry(player).or_insert_with(|| Queue {
+      deadline: now,
+      pending: VecDeque::new(),
+    });
+    let start = queue.pending.is_empty();
+    if start {
+      queue.deadline = now + cost;
+    }
+    queue.pending.push_back((cost, command));
+    start
+  }
+
+  /// Puts a command ahead of everything the player has queued, starting it right away whatever
+  /// the queue's capacity.
+  pub fn push_front(&mut self, player: usize, now: u64, cost: u64, command: T) {
+    let queue = self.queues.entry(player).or_insert_with(|| Queue {
+      deadline: now,
+      pending: VecDeque::new(),
+    });
+    queue.deadline = now + cost;
+    queue.pending.push_front((cost, command));
+  }
+
+  /// Command the player is executing.
+  pub fn front(&self, player: usize) -> Option<&T> {
+    self
+      .queues
+      .get(&player)
+      .and_then(|queue| queue.pending.front())
+      .map(|(_, command)| command)
+  }
+
+  /// Pops the earliest command completing at or before `now`, starting the next one of the
+  /// same player right where it ended.
+  pub fn pop_due(&mut self, now: u64) -> Option<(usize, T)> {
+    let (&player, queue) = self
+      .queues
+      .iter_mut()
+      .filter(|(_, queue)| !queue.pending.is_empty() && queue.deadline <= now)
+      .min_by_key(|(_, queue)| queue.deadline)?;
+    let (_, command) = queue.pending.pop_front()?;
+    if let Some(&(cost, _)) = queue.pending.front() {
+      queue.deadline += cost;
+    } else {
+      self.queues.remove(&player);
+    }
+    Some((player, command))
+  }
+
+  pub fn remove(&mut self, player: usize) {
+    self.queues.remove(&player);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Pops everything due at each of `times`, tagged with the time it came out.
+  fn drain(
+    scheduler: &mut Scheduler<&'static str>,
+    times: impl IntoIterator<Item = u64>,
+  ) -> Vec<(u64, usize, &'static str)> {
+    let mut done = Vec::new();
+    for now in times {
+      while let Some((player, command)) = scheduler.pop_due(now) {
+        done.push((now, player, command));
+      }
+    }
+    done
+  }
+
+  #[test]
+  fn commands_complete_in_deadline_order_across_players() {
+    let mut scheduler = Scheduler::new();
+    scheduler.push(1, 0, 7, "forward").unwrap();
+    scheduler.push(2, 0, 1, "inventory").unwrap();
+    scheduler.push(3, 2, 5, "look").unwrap();
+    scheduler.push(0, 0, 7, "left").unwrap();
+    assert_eq!(
+      drain(&mut scheduler, 0..10),
+      [
+        (1, 2, "inventory"),
+        (7, 0, "left"),
+        (7, 1, "forward"),
+        (7, 3, "look"),
+      ]
+    );
+  }
+
+  #[test]
+  fn queued_commands_start_where_the_previous_one_ended() {
+    let mut scheduler = Scheduler::new();
+    scheduler.push(0, 0, 7, "forward").unwrap();
+    scheduler.push(0, 0, 7, "right").unwrap();
+    scheduler.push(0, 5, 1, "inventory").unwrap();
+    scheduler.push(0, 5, 0, "connect_nbr").unwrap();
+    assert_eq!(
+      drain(&mut scheduler, 0..20),
+      [
+        (7, 0, "forward"),
+        (14, 0, "right"),
+        (15, 0, "inventory"),
+        (15, 0, "connect_nbr"),
+      ]
+    );
+
+    scheduler.push(0, 30, 7, "left").unwrap();
+    assert_eq!(drain(&mut scheduler, 30..40), [(37, 0, "left")]);
+  }
+
+  #[test]
+  fn late_pops_keep_the_chained_deadlines() {
+    let mut scheduler = Scheduler::new();
+    scheduler.push(0, 0, 7, "forward").unwrap();
+    scheduler.push(0, 0, 7, "right").unwrap();
+    scheduler.push(0, 0, 7, "left").unwrap();
+    assert_eq!(
+      drain(&mut scheduler, [15, 21]),
+      [(15, 0, "forward"), (15, 0, "right"), (21, 0, "left")]
+    );
+  }
+
+  #[test]
+  fn full_queues_hand_commands_back() {
+    let mut scheduler = Scheduler::new();
+    for _ in 0..MAX_PENDING_COMMANDS {
+      scheduler.push(0, 0, 1, "left").unwrap();
+    }
+    assert_eq!(scheduler.push(0, 0, 1, "right"), Err("right"));
+    scheduler.push(1, 0, 1, "right").unwrap();
+    scheduler.force_push(0, 0, 0, "ko");
+
+    let done = drain(&mut scheduler, 0..=MAX_PENDING_COMMANDS as u64);
+    let player = done
+      .iter()
+      .filter(|(_, player, _)| *player == 0)
+      .collect::<Vec<_>>();
+    assert_eq!(player.len(), MAX_PENDING_COMMANDS + 1);
+    assert_eq!(player.last(), Some(&&(10, 0, "ko")));
+    assert!(done.contains(&(1, 1, "right")));
+  }
+
+  #[test]
+  fn pushed_front_commands_start_right_away() {
+    let mut scheduler = Scheduler::new();
+    for _ in 0..MAX_PENDING_COMMANDS {
+      scheduler.push(0, 0, 7, "left").unwrap();
+    }
+    assert_eq!(drain(&mut scheduler, 0..=7), [(7, 0, "left")]);
+    scheduler.push_front(0, 7, 300, "incantation");
+    scheduler.push_front(1, 7, 300, "incantation");
+    assert_eq!(scheduler.push(0, 7, 7, "right"), Err("right"));
+
+    let done = drain(&mut scheduler, 8..=400);
+    assert_eq!(
+      done[..3],
+      [
+        (307, 0, "incantation"),
+        (307, 1, "incantation"),
+        (314, 0, "left")
+      ]
+    );
+    assert_eq!(done.len(), MAX_PENDING_COMMANDS + 1);
+    assert_eq!(done.last(), Some(&(370, 0, "left")));
+  }
+
+  #[test]
+  fn front_is_the_running_command() {
+    let mut scheduler = Scheduler::new();
+    assert_eq!(scheduler.push(0, 0, 7, "forward"), Ok(true));
+    assert_eq!(scheduler.push(0, 0, 42, "fork"), Ok(false));
+    assert_eq!(scheduler.front(0), Some(&"forward"));
+    assert_eq!(drain(&mut scheduler, 0..=7), [(7, 0, "forward")]);
+    assert_eq!(scheduler.front(0), Some(&"fork"));
+    assert_eq!(drain(&mut scheduler, 8..=49), [(49, 0, "fork")]);
+    assert_eq!(scheduler.front(0), None);
+    assert!(scheduler.force_push(0, 50, 0, "ko"));
+  }
+
+  #[test]
+  fn removed_players_complete_nothing() {
+    let mut scheduler = Scheduler::new();
+    scheduler.push(0, 0, 1, "left").unwrap();
+    scheduler.push(1, 0, 1, "right").unwrap();
+    scheduler.remove(0);
+    assert_eq!(drain(&mut scheduler, 0..5), [(1, 1, "right")]);
+  }
+}
@@ -0,0 +1,618 @@
+use {
+  crate::{
+    admin::{AdminCommand, AdminResponse},
+    clock::{Clock, ClockMode},
+    command::{INCANTATION_TIME, Task, cost},
+    connection::{ADMIN_ROLE, Connection, GRAPHIC_ROLE, Role},
+    gui,
+    scheduler::Scheduler,
+  },
+  common::{
+    protocol::{AiCommand, AiResponse, GuiEvent},
+    zappy::{Error, Event, GameState, Player, Position, World},
+  },
+  mio::{Events, Interest, Poll, Token, net::TcpListener},
+  std::{
+    collections::HashMap,
+    io::{self, ErrorKind},
+    net::{Ipv4Addr, SocketAddr},
+    time::{Duration, Instant},
+  },
+};
+
+const LISTENER: Token = Token(0);
+const EVENTS_CAPACITY: usize = 1024;
+/// How long the server keeps writing to slow clients once the game is over.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct Server {
+  world: World,
+  poll: Poll,
+  listener: TcpListener,
+  connections: HashMap<Token, Connection>,
+  /// Connection of every player in the game.
+  players: HashMap<usize, Token>,
+  next_token: usize,
+  scheduler: Scheduler<Task>,
+  clock: Clock,
+  /// Time units elapsed since the game started.
+  time: u64,
+  admin: bool,
+}
+
+impl Server {
+  /// Listens on every interface. With `admin`, clients connecting from this machine may take the
+  /// `ADMIN` role and drive the game clock.
+  pub fn bind(world: World, port: u16, admin: bool) -> io::Result<Self> {
+    let poll = Poll::new()?;
+    let mut listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)))?;
+    poll
+      .registry()
+      .register(&mut listener, LISTENER, Interest::READABLE)?;
+    Ok(Self {
+      poll,
+      listener,
+      connections: HashMap::new(),
+      players: HashMap::new(),
+      next_token: LISTENER.0 + 1,
+      scheduler: Scheduler::new(),
+      clock: Clock::new(world.frequency()),
+      time: 0,
+      admin,
+      world,
+    })
+  }
+
+  pub fn local_port(&self) -> io::Result<u16> {
+    Ok(self.listener.local_addr()?.port())
+  }
+
+  /// Serves every client and runs the game one time unit after the other until a team wins,
+  /// returning its name. Each unit is played on its own, whatever the clock's mode, so a seeded
+  /// game plays out the same when stepped through.
+  pub fn run(&mut self) -> io::Result<String> {
+    let mut events = Events::with_capacity(EVENTS_CAPACITY);
+    loop {
+      if let Err(err) = self.poll.poll(&mut events, self.clock.timeout()) {
+        if err.kind() == ErrorKind::Interrupted {
+          continue;
+        }
+        return Err(err);
+      }
+
+      for event in &events {
+        match event.token() {
+          LISTENER => self.accept(),
+          token if event.is_readable() || event.is_read_closed() => self.receive(token),
+          _ => {}
+        }
+      }
+
+      self.sync_frequency();
+      if self.clock.mode() != ClockMode::Paused {
+        self.run_commands();
+      }
+      for _ in 0..self.clock.advance() {
+        self.tick(1);
+        self.run_commands();
+      }
+      self.flush();
+
+      if let GameState::Ended(winner) = self.world.update_state() {
+        let winner = winner.clone();
+        self.shutdown(&winner, &mut events)?;
+        return Ok(winner);
+      }
+    }
+  }
+
+  /// Tells every client the game is over, then keeps writing until they all got their output or
+  /// `SHUTDOWN_TIMEOUT` runs out. AI clients are told they're dead, since they can't play on.
+  fn shutdown(&mut self, winner: &str, events: &mut Events) -> io::Result<()> {
+    self.notify(GuiEvent::Seg(winner.to_string()));
+    for connection in self.connections.values_mut() {
+      if let Role::Ai(_) = connection.role {
+        connection.send(AiResponse::Dead);
+      }
+      connection.close();
+    }
+
+    let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+    loop {
+      self.flush();
+      let timeout = deadline.saturating_duration_since(Instant::now());
+      if self.connections.is_empty() || timeout.is_zero() {
+        return Ok(());
+      }
+      match self.poll.poll(events, Some(timeout)) {
+        Err(err) if err.kind() != ErrorKind::Interrupted => return Err(err),
+        _ => {}
+      }
+    }
+  }
+
+  /// Accepts every pending client. A failure, such as running out of file descriptors, only
+  /// stops accepting until the listener is ready again.
+  fn accept(&mut self) {
+    loop {
+      let (mut stream, address) = match self.listener.accept() {
+        Ok(accepted) => accepted,
+        Err(err) if err.kind() == ErrorKind::WouldBlock => return,
+        Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+        Err(err) => {
+          eprintln!("failed to accept a client: {err}");
+          return;
+        }
+      };
+      let token = Token(self.next_token);
+      self.next_token += 1;
+      if let Err(err) =
+        self
+          .poll
+          .registry()
+          .register(&mut stream, token, Interest::READABLE | Interest::WRITABLE)
+      {
+        eprintln!("failed to register client {address}: {err}");
+        continue;
+      }
+      println!("client {address} connected");
+      let mut connection = Connection::new(stream, address);
+      connection.send(AiResponse::Welcome);
+      self.connections.insert(token, connection);
+    }
+  }
+
+  fn receive(&mut self, token: Token) {
+    let Some(connection) = self.connections.get_mut(&token) else {
+      return;
+    };
+    let open = match connection.receive() {
+      Ok(open) => open,
+      Err(err) => {
+        eprintln!("client {}: {err}", connection.address());
+        false
+      }
+    };
+    for line in connection.lines() {
+      self.handle_line(token, &line);
+    }
+    if !open {
+      self.disconnect(token);
+    }
+  }
+
+  fn handle_line(&mut self, token: Token, line: &str) {
+    let Some(connection) = self.connections.get_mut(&token) else {
+      return;
+    };
+    if connection.is_closing() {
+      return;
+    }
+    match connection.role {
+      Role::Dead => {}
+      Role::Pending => self.greet(token, line),
+      Role::Ai(player) => self.queue_command(player, line),
+      Role::Graphic => match line.parse() {
+        Ok(command) => gui::query(&mut self.world, command)
+          .into_iter()
+          .for_each(|reply| connection.send(reply)),
+        Err(err) => {
+          println!("monitor {}: {err}", connection.address());
+          connection.send(gui::rejected(&err));
+        }
+      },
+      Role::Admin => {
+        let response = match line.parse() {
+          Ok(command) => self.admin(command),
+          Err(err) => {
+            println!("admin {}: {err}", connection.address());
+            AdminResponse::Ko
+          }
+        };
+        if let Some(connection) = self.connections.get_mut(&token) {
+          connection.send(response);
+        }
+      }
+    }
+  }
+
+  fn admin(&mut self, command: AdminCommand) -> AdminResponse {
+    let mode = match command {
+      AdminCommand::Pause => ClockMode::Paused,
+      AdminCommand::Resume => ClockMode::Running,
+      AdminCommand::Step(steps) => ClockMode::Step(steps),
+      AdminCommand::Unlimited => ClockMode::Unlimited,
+      AdminCommand::Status => {
+        return AdminResponse::Status {
+          mode: self.clock.mode(),
+          time: self.time,
+          frequency: self.clock.frequency(),
+          seed: self.world.seed(),
+        };
+      }
+    };
+    match mode {
+      ClockMode::Running | ClockMode::Unlimited => self.world.resume(),
+      ClockMode::Paused | ClockMode::Step(_) => self.world.pause(),
+    }
+    println!("clock {mode} at time {}", self.time);
+    self.clock.set_mode(mode);
+    self.notify(GuiEvent::Smg(format!("clock {mode}")));
+    AdminResponse::Ok
+  }
+
+  /// Pushes an event to every monitor.
+  fn notify(&mut self, event: GuiEvent) {
+    let line = event.to_string();
+    self
+      .connections
+      .values_mut()
+      .filter(|c| c.role == Role::Graphic)
+      .for_each(|c| c.send(&line));
+  }
+
+  fn notify_player(&mut self, player: usize, event: fn(&Player) -> GuiEvent) {
+    if let Ok(player) = self.world.player(player) {
+      let event = event(player);
+      self.notify(event);
+    }
+  }
+
+  fn notify_tile(&mut self, position: Position) {
+    let event = gui::bct(&self.world, position);
+    self.notify(event);
+  }
+
+  fn queue_command(&mut self, player: usize, line: &str) {
+    let (task, cost) = match line.parse::<AiCommand>() {
+      Ok(command) => {
+        let cost = cost(&command);
+        (Task::Command(command), cost)
+      }
+      Err(err) => {
+        println!("player #{player}: {err}");
+        (Task::Reject, 0)
+      }
+    };
+    match self.scheduler.push(player, self.time, cost, task) {
+      Ok(true) => self.task_started(player),
+      Ok(false) => {}
+      Err(_) => {
+        println!("player #{player}: queue full, rejecting '{line}'");
+        self
+          .scheduler
+          .force_push(player, self.time, 0, Task::Reject);
+      }
+    }
+  }
+
+  /// Executes every command whose time has come, in the order they complete.
+  fn run_commands(&mut self) {
+    while let Some((player, task)) = self.scheduler.pop_due(self.time) {
+      let reply = match task {
+        Task::Command(command) => self.execute(player, command),
+        Task::FinishIncantation => self.finish_incantation(player),
+        Task::Reject => Some(AiResponse::Ko),
+      };
+      if let Some(reply) = reply
+        && let Some(connection) = self.connection_of(player)
+      {
+        connection.send(reply);
+      }
+      self.task_started(player);
+    }
+  }
+
+  /// Tells monitors a player started laying an egg, once its `Fork` reaches the front of its queue.
+  fn task_started(&mut self, player: usize) {
+    if let Some(Task::Command(AiCommand::Fork)) = self.scheduler.front(player) {
+      self.notify(GuiEvent::Pfk(player));
+    }
+  }
+
+  /// Runs a command against the world, returning the reply for the player who sent it.
+  fn execute(&mut self, player: usize, command: AiCommand) -> Option<AiResponse> {
+    let reply = match command {
+      AiCommand::Forward => self.world.forward(player).map(|_| {
+        self.notify_player(player, gui::ppo);
+        AiResponse::Ok
+      }),
+      AiCommand::Right => self.world.turn_right(player).map(|_| {
+        self.notify_player(player, gui::ppo);
+        AiResponse::Ok
+      }),
+      AiCommand::Left => self.world.turn_left(player).map(|_| {
+        self.notify_player(player, gui::ppo);
+        AiResponse::Ok
+      }),
+      AiCommand::Look => self.world.look(player).map(|look| AiResponse::from(&look)),
+      AiCommand::Inventory => self
+        .world
+        .player(player)
+        .map(|player| AiResponse::Inventory(*player.inventory())),
+      AiCommand::Broadcast(text) => self.broadcast(player, &text).map(|_| AiResponse::Ok),
+      AiCommand::ConnectNbr => self
+        .world
+        .player(player)
+        .and_then(|p| self.world.free_slots(p.team()))
+        .map(AiResponse::Slots),
+      AiCommand::Fork => self.world.lay_egg(player).map(|egg| {
+        if let Ok(egg) = self.world.egg(egg) {
+          let event = gui::enw(egg);
+          self.notify(event);
+        }
+        AiResponse::Ok
+      }),
+      AiCommand::Eject => self.eject(player),
+      AiCommand::Take(item) => self.world.take(player, item).map(|event| {
+        self.notify(GuiEvent::Pgt(player, item));
+        self.notify_item_moved(player, event);
+        AiResponse::Ok
+      }),
+      AiCommand::Set(item) => self.world.set(player, item).map(|event| {
+        self.notify(GuiEvent::Pdr(player, item));
+        self.notify_item_moved(player, event);
+        AiResponse::Ok
+      }),
+      AiCommand::Incantation => return self.start_incantation(player),
+    };
+    Some(reply.unwrap_or_else(|err| {
+      println!("player #{player}: {err}");
+      AiResponse::Ko
+    }))
+  }
+
+  fn notify_item_moved(&mut self, player: usize, event: Event) {
+    if let Event::ItemTaken { position, .. } | Event::ItemDropped { position, .. } = event {
+      self.notify_player(player, gui::pin);
+      self.notify_tile(position);
+    }
+  }
+
+  fn broadcast(&mut self, sender: usize, text: &str) -> Result<(), Error> {
+    self.notify(GuiEvent::Pbc(sender, text.to_string()));
+    let receivers = self
+      .world
+      .players()
+      .map(|p| p.id())
+      .filter(|&id| id != sender)
+      .collect::<Vec<_>>();
+    for receiver in receivers {
+      let direction = self.world.broadcast_direction(sender, receiver)?;
+      if let Some(connection) = self.connection_of(receiver) {
+        connection.send(AiResponse::Message(direction, text.to_string()));
+      }
+    }
+    Ok(())
+  }
+
+  fn eject(&mut self, player: usize) -> Result<AiResponse, Error> {
+    let events = self.world.eject(player)?;
+    if events.is_empty() {
+      return Ok(AiResponse::Ko);
+    }
+    self.notify(GuiEvent::Pex(player));
+    for event in events {
+      match event {
+        Event::PlayerEjected { id, direction, .. } => {
+          self.notify_player(id, gui::ppo);
+          if let Some(connection) = self.connection_of(id) {
+            connection.send(AiResponse::Eject(direction));
+          }
+        }
+        Event::EggDestroyed { id, .. } => self.notify(GuiEvent::Edi(id)),
+        _ => {}
+      }
+    }
+    Ok(AiResponse::Ok)
+  }
+
+  /// Checks the elevation conditions as soon as an incantation comes up, telling every
+  /// participant it started and scheduling its outcome.
+  fn start_incantation(&mut self, leader: usize) -> Option<AiResponse> {
+    let participants = match self.world.start_incantation(leader) {
+      Ok(incantation) => {
+        let participants = incantation.participants().to_vec();
+        let event = GuiEvent::Pic(
+          incantation.position(),
+          incantation.level(),
+          participants.clone(),
+        );
+        self.notify(event);
+        participants
+      }
+      Err(err) => {
+        println!("player #{leader}: {err}");
+        return Some(AiResponse::Ko);
+      }
+    };
+    for id in participants {
+      if let Some(connection) = self.connection_of(id) {
+        connection.send(AiResponse::ElevationUnderway);
+      }
+    }
+    self
+      .scheduler
+      .push_front(leader, self.time, INCANTATION_TIME, Task::FinishIncantation);
+    None
+  }
+
+  /// Gives every player the incantation started with its outcome: their new level, or `ko` if it
+  /// failed or they left its tile.
+  fn finish_incantation(&mut self, leader: usize) -> Option<AiResponse> {
+    let incantation = self.world.incantation(leader)?;
+    let (position, participants) = (incantation.position(), incantation.participants().to_vec());
+    let promoted = match self.world.finish_incantation(leader) {
+      Ok(promoted) => promoted,
+      Err(err) => {
+        println!("player #{leader}: {err}");
+        self.abandon_incantation(position, &participants);
+        return None;
+      }
+    };
+    self.notify(GuiEvent::Pie(position, true));
+    self.notify_tile(position);
+    for id in participants {
+      let reply = if promoted.contains(&id) {
+        self.notify_player(id, gui::plv);
+        match self.world.player(id) {
+          Ok(player) => AiResponse::CurrentLevel(player.level()),
+          Err(_) => continue,
+        }
+      } else {
+        AiResponse::Ko
+      };
+      if let Some(connection) = self.connection_of(id) {
+        connection.send(reply);
+      }
+    }
+    None
+  }
+
+  /// Answers the team name, `GRAPHIC` or `ADMIN` a client sends back after `WELCOME`.
+  fn greet(&mut self, token: Token, line: &str) {
+    let Some(connection) = self.connections.get_mut(&token) else {
+      return;
+    };
+    if line == GRAPHIC_ROLE {
+      connection.role = Role::Graphic;
+      gui::dump(&self.world)
+        .iter()
+        .for_each(|line| connection.send(line));
+      return;
+    }
+    if line == ADMIN_ROLE {
+      if self.admin && connection.address().ip().is_loopback() {
+        connection.role = Role::Admin;
+      } else {
+        println!("client {} refused the admin role", connection.address());
+        connection.send(AiResponse::Ko);
+      }
+      return;
+    }
+
+    let address = connection.address();
+    let (id, egg) = match self.world.add_player(line) {
+      Ok(hatched) => hatched,
+      Err(err) => {
+        match err {
+          Error::TeamIsFull(_) | Error::TeamDoesntExist(_) => {
+            println!("client {address} can't join: {err}")
+          }
+          _ => eprintln!("client {address} can't join: {err}"),
+        }
+        if let Some(connection) = self.connections.get_mut(&token) {
+          connection.send(AiResponse::Ko);
+        }
+        return;
+      }
+    };
+    println!("client {address} joined team '{line}' as player #{id}");
+    let slots = self.world.free_slots(line).unwrap_or(0);
+    let size = AiResponse::MapSize(self.world.x(), self.world.y());
+    self.players.insert(id, token);
+    if let Some(connection) = self.connections.get_mut(&token) {
+      connection.role = Role::Ai(id);
+      connection.send(AiResponse::Slots(slots));
+      connection.send(size);
+    }
+    self.notify(GuiEvent::Ebo(egg));
+    self.notify_player(id, gui::pnw);
+  }
+
+  /// Follows frequency changes made through the world, such as a monitor's `sst`. Every timer
+  /// counts time units, so only their length changes.
+  fn sync_frequency(&mut self) {
+    let frequency = self.world.frequency();
+    if frequency != self.clock.frequency() {
+      println!("frequency changed to {frequency}");
+      self.clock.set_frequency(frequency);
+      self.notify(GuiEvent::Sst(frequency));
+    }
+  }
+
+  fn flush(&mut self) {
+    let done = self
+      .connections
+      .iter_mut()
+      .filter_map(|(&token, connection)| match connection.flush() {
+        Ok(()) => connection.is_closed().then_some(token),
+        Err(err) => {
+          eprintln!("client {}: {err}", connection.address());
+          Some(token)
+        }
+      })
+      .collect::<Vec<_>>();
+    for token in done {
+      self.disconnect(token);
+    }
+  }
+
+  fn connection_of(&mut self, player: usize) -> Option<&mut Connection> {
+    let token = self.players.get(&player)?;
+    self.connections.get_mut(token)
+  }
+
+  fn disconnect(&mut self, token: Token) {
+    let Some(mut connection) = self.connections.remove(&token) else {
+      return;
+    };
+    let _ = self.poll.registry().deregister(connection.stream_mut());
+    if let Role::Ai(id) = connection.role {
+      self.players.remove(&id);
+      self.scheduler.remove(id);
+      self.notify(GuiEvent::Pdi(id));
+      let incantation = self
+        .world
+        .incantation(id)
+        .map(|i| (i.position(), i.participants().to_vec()));
+      if let Err(err) = self.world.remove_player(id) {
+        eprintln!("failed to remove player #{id}: {err}");
+      }
+      if let Some((position, participants)) = incantation {
+        self.abandon_incantation(position, &participants);
+      }
+    }
+    println!("client {} disconnected", connection.address());
+  }
+
+  fn tick(&mut self, time_units: u64) {
+    self.time += time_units;
+    for event in self.world.tick(time_units) {
+      match event {
+        Event::PlayerDied { id, team, .. } => {
+          println!("player #{id} of team '{team}' starved to death");
+          self.scheduler.remove(id);
+          self.notify(GuiEvent::Pdi(id));
+          if let Some(connection) = self.connection_of(id) {
+            connection.send(AiResponse::Dead);
+            connection.role = Role::Dead;
+            connection.close();
+          }
+          self.players.remove(&id);
+        }
+        Event::ResourcesSpawned { positions } => {
+          for position in positions {
+            self.notify_tile(position);
+          }
+        }
+        Event::IncantationAbandoned {
+          position,
+          participants,
+          ..
+        } => self.abandon_incantation(position, &participants),
+        _ => {}
+      }
+    }
+  }
+
+  /// Fails an incantation, telling each of its participants.
+  fn abandon_incantation(&mut self, position: Position, participants: &[usize]) {
+    self.notify(GuiEvent::Pie(position, false));
+    for &id in participants {
+      if let Some(connection) = self.connection_of(id) {
+        connection.send(AiResponse::Ko);
+      }
+    }
+  }
+}